@@ -7,27 +7,37 @@
 //!
 //! - `helm voyage new|list|end` — lifecycle management, no voyage context needed.
 //! - `helm --voyage <id> <command>` — everything else, operating within a voyage.
+//!   These warn about steers left pending by an interrupted invocation.
 //!
 //! The `--voyage` flag takes a full UUID or unambiguous prefix.
 
+mod logbook;
 mod observe;
+mod pending;
+mod policy;
+mod search;
 mod slate;
+mod steer;
 mod target;
 mod voyage;
 
 use std::path::PathBuf;
 
-use clap::{ArgGroup, Parser, Subcommand};
+use clap::{Parser, Subcommand};
 use uuid::Uuid;
 
 use crate::{
+    hooks::{self, Event, HookAction, HookInput},
     identity,
-    model::{CommentTarget, Steer, Voyage},
-    steer,
+    model::{PendingStatus, Provenance, Voyage},
     storage::Storage,
 };
 
+use logbook::LogbookCommand;
+use pending::PendingCommand;
+use search::StatusFilter;
 use slate::SlateCommand;
+use steer::{Mode, SteerCommand};
 use target::ObserveTarget;
 use voyage::VoyageCommand;
 
@@ -45,15 +55,57 @@ const WORKFLOW_HELP: &str = r#"Workflow: advancing an issue
   2. helm observe --voyage a3b --as dyreby github-issue 42
   3. helm steer --voyage a3b --as john-agent --role coder --method "claude-opus-4, thinking high" --summary "Plan looks good" comment --issue 42 --body "Here's my plan: ..."
   4. helm voyage end --voyage a3b --status "Merged PR #45"
+  5. helm logbook show --voyage a3b --format markdown
+  6. helm logbook entry --voyage a3b 2 --observation 1 --full
+  7. helm logbook verify --voyage a3b
+
+Search (across voyages):
+  helm search gyroscope overflow --identity john-agent --since 2026-10-01 --status ended
 
 Identity (--as):
   --as is optional when identity is configured elsewhere.
   Resolution order: --as flag → HELM_IDENTITY env var → ~/.helm/config.toml
+  Optional signing key, checked by `helm logbook verify`:
+    ssh-keygen -t ed25519 -N "" -C dyreby -f ~/.helm/keys/dyreby
 
 Observe:
   helm observe --voyage a3b --as dyreby file-contents --read src/widget.rs
   helm observe --voyage a3b --as dyreby github-pr 42
-  helm observe --voyage a3b --as dyreby github-repo"#;
+  helm observe --voyage a3b --as dyreby github-repo
+
+Steer:
+  helm steer --voyage a3b --role planner --method conversation --summary "Track the crash" create-issue --title "Widget crashes on resize" --body "..." --label bug
+  helm steer --voyage a3b --role planner --method conversation --summary "Triage" label --issue 42 --add bug --remove needs-triage
+  helm steer --voyage a3b --role coder --method conversation --summary "Fix ready" create-pr --head fix-widget --base main --title "Fix widget crash" --body "Closes #42" --reviewer dyreby
+  helm steer --voyage a3b --role coder --method conversation --summary "Ready for eyes" request-review 45 --reviewer dyreby --team core
+  helm steer --voyage a3b --role reviewer --method conversation --summary "Thanks" react --pr 45 --comment 1234 --reaction heart
+  helm steer --voyage a3b --role reviewer --method conversation --summary "Two nits" submit-review 45 --verdict comment --body "Close." --inline 'src/widget.rs:10-12=Extract this.'
+  helm steer --voyage a3b --role reviewer --method conversation --summary "Approved, CI green" merge-pr 45 --strategy squash --delete-branch
+    (merge-pr pins to the head observed via `github-pr 45`, or to --expected-head)
+  Add --dry-run before the subcommand to print the steer, its gh invocations,
+  and the bearing it would seal, without performing or recording anything.
+
+Long-form text:
+  --body, --comment, and --notes take `-` to read stdin, or a sibling -file flag:
+  helm steer --voyage a3b --role coder --method conversation --summary "Plan" comment --issue 42 --body-file plan.md
+  Add --template to render {{intent}}, {{slate}}, and {{logbook}} into the text:
+  printf "Working on: {{intent}}\n\nSeen so far:\n{{slate}}\n" | helm steer --voyage a3b ... --template comment --issue 42 --body -
+
+Propose and approve:
+  helm steer --voyage a3b --as john-agent --role coder --method "claude-opus-4" --summary "CI green" --propose merge-pr 45 --strategy squash
+    → prints a proposal ID (e.g. 3); see `helm pending list --voyage a3b`
+  helm steer --voyage a3b --as dyreby --role reviewer --method human --summary "Verified the diff" approve 3
+    (performs as john-agent; the logbook entry records both identities)
+
+Policy:
+  ~/.helm/policy.toml limits which steers each identity and role may perform:
+    [[allow]]
+    identity = "john-agent"
+    steers = ["comment", "create-pr"]   # names as above; "*" for all
+    repos = ["dyreby/helm"]             # optional
+    targets = [42, 45]                  # optional issue/PR numbers
+  Without the file, everything is allowed. With it, anything no rule allows is
+  denied; approvals are checked against the approver. See: helm denials --voyage a3b"#;
 
 #[derive(Debug, Subcommand)]
 pub enum Command {
@@ -69,6 +121,58 @@ pub enum Command {
         command: SlateCommand,
     },
 
+    /// List and resolve steers interrupted before they were recorded.
+    Pending {
+        #[command(subcommand)]
+        command: PendingCommand,
+    },
+
+    /// Read a voyage's logbook.
+    Logbook {
+        #[command(subcommand)]
+        command: LogbookCommand,
+    },
+
+    /// Search intents, logbooks, and sealed observations across every voyage.
+    ///
+    /// Each word must appear; a trailing `*` matches a prefix. Best matches first.
+    /// Hits name the entry and observation they were found in, for `helm logbook entry`.
+    Search {
+        /// What to look for.
+        #[arg(required = true)]
+        query: Vec<String>,
+
+        /// Only entries recorded by this identity, and what they sealed.
+        #[arg(long)]
+        identity: Option<String>,
+
+        /// Only text recorded on or after this date or timestamp (UTC).
+        #[arg(long, value_parser = search::parse_since)]
+        since: Option<jiff::Timestamp>,
+
+        /// Only text recorded on or before this date, or before this timestamp (UTC).
+        #[arg(long, value_parser = search::parse_until)]
+        until: Option<jiff::Timestamp>,
+
+        /// Only active or ended voyages.
+        #[arg(long, value_enum)]
+        status: Option<StatusFilter>,
+
+        /// At most this many hits.
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// List steers the policy refused.
+    ///
+    /// Steers are checked against `~/.helm/policy.toml` before they are performed;
+    /// each refusal is recorded here with who attempted it and why it was denied.
+    Denials {
+        /// Voyage ID: full UUID or unambiguous prefix (e.g. `a3b`).
+        #[arg(long)]
+        voyage: String,
+    },
+
     /// Observe the world and add to the slate.
     ///
     /// Pure read, no side effects, repeatable.
@@ -97,7 +201,11 @@ pub enum Command {
     /// Steer: perform an intent-based action that mutates collaborative state.
     ///
     /// Seals a bearing from the slate, performs the action,
-    /// records one logbook entry, and clears the slate.
+    /// and records one logbook entry. The steer is journaled before the
+    /// action runs; if helm is interrupted, it is left pending for
+    /// `helm pending resolve`.
+    /// What the action produced (created IDs, URLs) is recorded with the entry
+    /// and written to stdout as JSON.
     Steer {
         /// Voyage ID: full UUID or unambiguous prefix (e.g. `a3b`).
         #[arg(long)]
@@ -121,8 +229,28 @@ pub enum Command {
         #[arg(long)]
         summary: String,
 
+        /// Show what would happen instead of steering.
+        ///
+        /// Prints the built steer, the `gh` invocations it would run, and the
+        /// bearing it would seal, as JSON. Touches neither GitHub nor the voyage.
+        #[arg(long, conflicts_with = "propose")]
+        dry_run: bool,
+
+        /// Seal the steer into a proposal instead of performing it.
+        ///
+        /// A different identity performs it with `helm steer approve <id>`.
+        /// Prints the proposal ID.
+        #[arg(long)]
+        propose: bool,
+
+        /// Render `{{intent}}`, `{{slate}}`, and `{{logbook}}` in the steer's text.
+        ///
+        /// Applies to every title, body, comment, and note, however it was given.
+        #[arg(long)]
+        template: bool,
+
         #[command(subcommand)]
-        action: SteerAction,
+        action: SteerCommand,
     },
 
     /// Log a deliberate state without mutating collaborative state.
@@ -157,31 +285,9 @@ pub enum Command {
     },
 }
 
-/// Steer subcommands.
-#[derive(Debug, Subcommand)]
-pub enum SteerAction {
-    /// Comment on an issue, PR, or inline review thread.
-    #[command(group(ArgGroup::new("target").required(true).args(["issue", "pr"])))]
-    Comment {
-        /// Comment on this issue number.
-        #[arg(long, conflicts_with = "pr")]
-        issue: Option<u64>,
-
-        /// Comment on this PR number.
-        #[arg(long, conflicts_with = "issue")]
-        pr: Option<u64>,
-
-        /// Reply to this inline review comment ID (requires `--pr`).
-        #[arg(long, requires = "pr")]
-        reply_to: Option<u64>,
-
-        /// Comment body.
-        #[arg(long)]
-        body: String,
-    },
-}
-
 /// Run the CLI, returning an error message on failure.
+// One arm per command — the dispatch grows with the CLI.
+#[allow(clippy::too_many_lines)]
 pub fn run(storage: &Storage) -> Result<(), String> {
     let cli = Cli::parse();
 
@@ -191,19 +297,82 @@ pub fn run(storage: &Storage) -> Result<(), String> {
             VoyageCommand::List => voyage::cmd_list(storage),
             VoyageCommand::End { voyage, status } => {
                 let voyage = resolve_voyage(storage, &voyage)?;
+                warn_pending_steers(storage, &voyage)?;
                 voyage::cmd_end(storage, &voyage, status.as_deref())
             }
         },
         Command::Slate { command } => match command {
             SlateCommand::List { voyage } => {
                 let voyage = resolve_voyage(storage, &voyage)?;
+                warn_pending_steers(storage, &voyage)?;
                 slate::cmd_list(storage, &voyage)
             }
             SlateCommand::Clear { voyage } => {
                 let voyage = resolve_voyage(storage, &voyage)?;
+                warn_pending_steers(storage, &voyage)?;
                 slate::cmd_clear(storage, &voyage)
             }
         },
+        Command::Pending { command } => match command {
+            PendingCommand::List { voyage } => {
+                let voyage = resolve_voyage(storage, &voyage)?;
+                pending::cmd_list(storage, &voyage)
+            }
+            PendingCommand::Resolve {
+                voyage,
+                id,
+                performed,
+                abandoned: _,
+            } => {
+                let voyage = resolve_voyage(storage, &voyage)?;
+                pending::cmd_resolve(storage, &voyage, id, performed)
+            }
+        },
+        Command::Logbook { command } => match command {
+            LogbookCommand::Show { voyage, format } => {
+                let voyage = resolve_voyage(storage, &voyage)?;
+                warn_pending_steers(storage, &voyage)?;
+                logbook::cmd_show(storage, &voyage, format)
+            }
+            LogbookCommand::Entry {
+                voyage,
+                number,
+                observation,
+                full,
+            } => {
+                let voyage = resolve_voyage(storage, &voyage)?;
+                warn_pending_steers(storage, &voyage)?;
+                logbook::cmd_entry(storage, &voyage, number, observation, full)
+            }
+            LogbookCommand::Verify {
+                voyage,
+                require_signatures,
+            } => {
+                let voyage = resolve_voyage(storage, &voyage)?;
+                warn_pending_steers(storage, &voyage)?;
+                logbook::cmd_verify(storage, &voyage, require_signatures)
+            }
+        },
+        Command::Search {
+            query,
+            identity,
+            since,
+            until,
+            status,
+            limit,
+        } => search::cmd_search(
+            storage,
+            &query.join(" "),
+            identity,
+            since,
+            until,
+            status,
+            limit,
+        ),
+        Command::Denials { voyage } => {
+            let voyage = resolve_voyage(storage, &voyage)?;
+            policy::cmd_denials(storage, &voyage)
+        }
         Command::Observe {
             voyage,
             identity,
@@ -211,6 +380,7 @@ pub fn run(storage: &Storage) -> Result<(), String> {
             out,
         } => {
             let voyage = resolve_voyage(storage, &voyage)?;
+            warn_pending_steers(storage, &voyage)?;
             // Identity is resolved lazily inside cmd_observe — local targets don't require it.
             observe::cmd_observe(storage, &voyage, identity.as_deref(), &target, out)
         }
@@ -220,13 +390,44 @@ pub fn run(storage: &Storage) -> Result<(), String> {
             role,
             method,
             summary,
+            dry_run,
+            propose,
+            template,
             action,
         } => {
+            // A dry run leaves the voyage untouched — schema upgrades included.
+            let read_only;
+            let storage = if dry_run {
+                read_only = storage.read_only();
+                &read_only
+            } else {
+                storage
+            };
             let voyage = resolve_voyage(storage, &voyage)?;
-            let identity = identity::resolve_identity(identity.as_deref())?;
-            cmd_steer(
-                storage, &voyage, &identity, &role, &method, &summary, &action,
-            )
+            warn_pending_steers(storage, &voyage)?;
+            let provenance = Provenance {
+                identity: identity::resolve_identity(identity.as_deref())?,
+                role,
+                method,
+            };
+            let mode = Mode::from_flags(dry_run, propose);
+            match action {
+                SteerCommand::Action(action) => steer::cmd_steer(
+                    storage,
+                    &voyage,
+                    &provenance,
+                    &summary,
+                    &action,
+                    mode,
+                    template,
+                ),
+                SteerCommand::Approve { .. } if template => {
+                    Err("--template doesn't apply to approve".into())
+                }
+                SteerCommand::Approve { id } => {
+                    steer::cmd_approve(storage, &voyage, &provenance, &summary, id, mode)
+                }
+            }
         }
         Command::Log {
             voyage,
@@ -237,86 +438,51 @@ pub fn run(storage: &Storage) -> Result<(), String> {
             status,
         } => {
             let voyage = resolve_voyage(storage, &voyage)?;
-            let identity = identity::resolve_identity(identity.as_deref())?;
-            cmd_log(
-                storage, &voyage, &identity, &role, &method, &summary, &status,
-            )
+            warn_pending_steers(storage, &voyage)?;
+            let provenance = Provenance {
+                identity: identity::resolve_identity(identity.as_deref())?,
+                role,
+                method,
+            };
+            cmd_log(storage, &voyage, &provenance, &summary, &status)
         }
     }
 }
 
-fn cmd_steer(
-    storage: &Storage,
-    voyage: &Voyage,
-    identity: &str,
-    role: &str,
-    method: &str,
-    summary: &str,
-    action: &SteerAction,
-) -> Result<(), String> {
-    let gh_config = gh_config_dir(identity)?;
-
-    // 1. Build the typed steer action from CLI args.
-    let steer_action = build_steer_action(action);
-
-    // 2. Perform the action — mutate collaborative state.
-    //    Happens before recording so a failed action leaves the logbook untouched.
-    steer::perform(&steer_action, &gh_config)?;
-
-    // 3. Seal the slate, record one logbook entry, and clear the slate — one transaction.
-    storage
-        .record_steer(voyage.id, &steer_action, summary, identity, role, method)
-        .map_err(|e| format!("failed to record steer: {e}"))?;
-
-    eprintln!("Steered: {}", describe_steer_action(action));
-    Ok(())
-}
-
 fn cmd_log(
     storage: &Storage,
     voyage: &Voyage,
-    identity: &str,
-    role: &str,
-    method: &str,
+    provenance: &Provenance,
     summary: &str,
     status: &str,
 ) -> Result<(), String> {
+    let slate = storage
+        .load_slate(voyage.id)
+        .map_err(|e| format!("failed to load slate: {e}"))?;
+    let hooks = hooks::load_hooks()?;
+    let mut hook_input = HookInput {
+        event: Event::PreLog,
+        voyage_id: voyage.id,
+        provenance,
+        summary,
+        action: HookAction::Log { status },
+        slate: &slate,
+    };
+    hooks
+        .run(&hook_input)
+        .map_err(|e| format!("log aborted by pre-log hook: {e}"))?;
+
     // Seal the slate, record one logbook entry, and clear the slate — one transaction.
     storage
-        .record_log(voyage.id, status, summary, identity, role, method)
+        .record_log(voyage.id, status, summary, provenance)
         .map_err(|e| format!("failed to record log entry: {e}"))?;
 
     eprintln!("Logged: {status}");
+    hook_input.event = Event::PostLog;
+    steer::warn_post_hooks(&hooks, &hook_input);
     Ok(())
 }
 
-/// Convert CLI steer args to the typed `Steer` model.
-fn build_steer_action(action: &SteerAction) -> Steer {
-    match action {
-        SteerAction::Comment {
-            issue,
-            pr,
-            reply_to,
-            body,
-        } => {
-            // Clap's ArgGroup ensures exactly one of --issue or --pr is present.
-            let (number, target) = match (issue, pr, reply_to) {
-                (Some(n), None, None) => (*n, CommentTarget::Issue),
-                (None, Some(n), None) => (*n, CommentTarget::PullRequest),
-                (None, Some(n), Some(id)) => {
-                    (*n, CommentTarget::ReviewFeedback { comment_id: *id })
-                }
-                _ => unreachable!("clap ArgGroup guarantees --issue or --pr is present"),
-            };
-            Steer::Comment {
-                number,
-                body: body.clone(),
-                target,
-            }
-        }
-    }
-}
-
 /// Resolve the `GH_CONFIG_DIR` for a given identity.
 ///
 /// Each identity has its own config directory under `~/.helm/gh-config/<identity>/`.
@@ -336,21 +502,37 @@ pub(super) fn gh_config_dir(identity: &str) -> Result<PathBuf, String> {
     Ok(config_dir)
 }
 
-/// Short human-readable description of what was steered.
-fn describe_steer_action(action: &SteerAction) -> String {
-    match action {
-        SteerAction::Comment {
-            issue,
-            pr,
-            reply_to,
-            body: _,
-        } => match (issue, pr, reply_to) {
-            (Some(n), None, None) => format!("comment on issue #{n}"),
-            (None, Some(n), None) => format!("comment on PR #{n}"),
-            (None, Some(n), Some(id)) => format!("reply to review comment {id} on PR #{n}"),
-            _ => "comment".to_string(),
-        },
+/// Warn on stderr about steers an interrupted invocation left pending,
+/// and note proposals awaiting approval.
+///
+/// Repeated on every voyage-scoped command until they are resolved.
+fn warn_pending_steers(storage: &Storage, voyage: &Voyage) -> Result<(), String> {
+    let pending = storage
+        .load_pending_steers(voyage.id)
+        .map_err(|e| format!("failed to load pending steers: {e}"))?;
+
+    let short_id = &voyage.id.to_string()[..8];
+    for p in &pending {
+        if p.status == PendingStatus::Proposed {
+            eprintln!(
+                "note: steer {} ({}) proposed by {} awaits approval",
+                p.id,
+                steer::describe_steer(&p.steer, None),
+                p.provenance.identity,
+            );
+            continue;
+        }
+        eprintln!(
+            "warning: steer {} ({}) was started at {} but never recorded — \
+             it may or may not have happened.\n  \
+             Check, then run: helm pending resolve --voyage {short_id} {} --performed|--abandoned",
+            p.id,
+            steer::describe_steer(&p.steer, None),
+            p.started_at,
+            p.id,
+        );
     }
+    Ok(())
 }
 
 /// Resolve a voyage reference (full UUID or unambiguous prefix) to a voyage.
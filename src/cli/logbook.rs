@@ -0,0 +1,703 @@
+//! Logbook commands: read back a voyage's story.
+//!
+//! Entries are numbered from 1 in the order they were recorded.
+
+use clap::{Subcommand, ValueEnum};
+use serde::Serialize;
+
+use crate::{
+    model::{
+        DirectoryListing, EntryKind, FileContent, LogbookEntry, Observation, Payload, Voyage,
+        VoyageStatus,
+    },
+    storage::{ChainProblem, ChainReport, Storage},
+};
+
+use super::{observe::describe_observe, steer::describe_steer};
+
+#[derive(Debug, Subcommand)]
+pub enum LogbookCommand {
+    /// Show a voyage's logbook: every steer and log, who recorded it, and why.
+    ///
+    /// Each entry lists its provenance, summary, and the targets its bearing was sealed from.
+    Show {
+        /// Voyage ID: full UUID or unambiguous prefix (e.g. `a3b`).
+        #[arg(long)]
+        voyage: String,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+
+    /// Show one entry and what was sealed into its bearing.
+    ///
+    /// Each observation is listed with a short summary of its payload.
+    /// `--observation` narrows to one; `--full` prints JSON instead — the
+    /// whole entry, or just that observation with its payload in full.
+    Entry {
+        /// Voyage ID: full UUID or unambiguous prefix (e.g. `a3b`).
+        #[arg(long)]
+        voyage: String,
+
+        /// Entry number, as shown by `helm logbook show`.
+        number: usize,
+
+        /// Only this observation of the bearing, counting from 1.
+        #[arg(long)]
+        observation: Option<usize>,
+
+        /// Print JSON in full instead of summaries.
+        #[arg(long)]
+        full: bool,
+    },
+
+    /// Check the logbook against its hash chain and signatures.
+    ///
+    /// Each entry's hash covers the one before it, so rewriting, deleting, or
+    /// reordering entries, or altering what a bearing observed, breaks the
+    /// chain. Prints the head hash; keep it elsewhere to pin the whole logbook.
+    /// Entries are checked against their signer's key in `~/.helm/keys/`;
+    /// unsigned ones are flagged. Exits non-zero if anything fails to verify.
+    Verify {
+        /// Voyage ID: full UUID or unambiguous prefix (e.g. `a3b`).
+        #[arg(long)]
+        voyage: String,
+
+        /// Fail on unsigned entries, and on signatures with no key to check them.
+        #[arg(long)]
+        require_signatures: bool,
+    },
+}
+
+/// How `helm logbook show` renders the logbook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Plain text for the terminal.
+    Text,
+
+    /// Markdown, for pasting into issues and PRs.
+    Markdown,
+
+    /// The voyage and its full entries, bearings included.
+    Json,
+}
+
+/// What `--format json` prints.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogbookJson<'a> {
+    voyage: &'a Voyage,
+    entries: &'a [LogbookEntry],
+}
+
+pub(super) fn cmd_show(storage: &Storage, voyage: &Voyage, format: Format) -> Result<(), String> {
+    let entries = storage
+        .load_logbook(voyage.id)
+        .map_err(|e| format!("failed to load logbook: {e}"))?;
+
+    let output = match format {
+        Format::Text => render_text(voyage, &entries),
+        Format::Markdown => render_markdown(voyage, &entries),
+        Format::Json => serde_json::to_string_pretty(&LogbookJson {
+            voyage,
+            entries: &entries,
+        })
+        .map_err(|e| format!("failed to serialize logbook: {e}"))?,
+    };
+    println!("{output}");
+    Ok(())
+}
+
+fn render_text(voyage: &Voyage, entries: &[LogbookEntry]) -> String {
+    let mut lines = vec![format!("Voyage: {}", voyage.intent)];
+    if let Some(ended) = describe_ended(&voyage.status) {
+        lines.push(ended);
+    }
+    lines.push(String::new());
+    if entries.is_empty() {
+        lines.push("No entries".to_string());
+    }
+
+    for (i, entry) in entries.iter().enumerate() {
+        lines.extend(entry_lines(i + 1, entry));
+        lines.push(format!("   bearing: {}", bearing_targets(entry)));
+        if let Some(approval) = describe_approval(entry) {
+            lines.push(format!("   {approval}"));
+        }
+    }
+    lines.join("\n")
+}
+
+/// An entry's headline, provenance, and summary, as plain text.
+fn entry_lines(number: usize, entry: &LogbookEntry) -> Vec<String> {
+    let (kind, what) = headline(entry);
+    let mut lines = vec![
+        format!("── {kind} {number} ── {what}"),
+        format!("   {}", describe_provenance(entry)),
+    ];
+    lines.extend(entry.bearing.summary.lines().map(|l| format!("   {l}")));
+    lines
+}
+
+pub(super) fn cmd_entry(
+    storage: &Storage,
+    voyage: &Voyage,
+    number: usize,
+    observation: Option<usize>,
+    full: bool,
+) -> Result<(), String> {
+    let entry = storage
+        .load_logbook_entry(voyage.id, number)
+        .map_err(|e| format!("failed to load logbook entry {number}: {e}"))?;
+
+    let observations = &entry.bearing.observations;
+    let selected = match observation {
+        Some(k) => {
+            let o = k
+                .checked_sub(1)
+                .and_then(|i| observations.get(i))
+                .ok_or_else(|| {
+                    format!(
+                        "entry {number} has {} observation(s); there is no observation {k}",
+                        observations.len()
+                    )
+                })?;
+            Some((k, o))
+        }
+        None => None,
+    };
+
+    let output = match (selected, full) {
+        (Some((_, o)), true) => serde_json::to_string_pretty(o),
+        (None, true) => serde_json::to_string_pretty(&entry),
+        (Some((k, o)), false) => Ok(describe_observation(k, o).join("\n")),
+        (None, false) => Ok(render_entry(number, &entry)),
+    }
+    .map_err(|e| format!("failed to serialize logbook entry: {e}"))?;
+    println!("{output}");
+    Ok(())
+}
+
+pub(super) fn cmd_verify(
+    storage: &Storage,
+    voyage: &Voyage,
+    require_signatures: bool,
+) -> Result<(), String> {
+    let report = storage
+        .verify_logbook(voyage.id)
+        .map_err(|e| format!("failed to verify logbook: {e}"))?;
+    println!("{}", render_report(&report, require_signatures));
+    match failures(&report, require_signatures) {
+        0 => Ok(()),
+        n => Err(format!("logbook failed verification: {n} problem(s)")),
+    }
+}
+
+/// Problems that fail verification: all of them, or only evidence of
+/// tampering unless signatures are required.
+fn failures(report: &ChainReport, require_signatures: bool) -> usize {
+    report
+        .problems
+        .iter()
+        .filter(|p| require_signatures || !p.is_unverified())
+        .count()
+}
+
+fn render_report(report: &ChainReport, require_signatures: bool) -> String {
+    let mut lines: Vec<_> = report
+        .problems
+        .iter()
+        .filter(|p| !matches!(p, ChainProblem::Unsigned { .. }))
+        .map(describe_problem)
+        .collect();
+
+    // One line for all unsigned entries: without keys, that's every entry.
+    let unsigned: Vec<_> = report
+        .problems
+        .iter()
+        .filter_map(|p| match p {
+            ChainProblem::Unsigned { entry, signer } => Some(format!("{entry} ({signer})")),
+            _ => None,
+        })
+        .collect();
+    if !unsigned.is_empty() {
+        lines.push(format!("unsigned: entries {}", unsigned.join(", ")));
+    }
+
+    if failures(report, require_signatures) == 0 {
+        lines.push(format!(
+            "Verified {} entries, {} signed",
+            report.entries, report.signed
+        ));
+    }
+    lines.push(format!(
+        "Head: {}",
+        report.head.as_deref().unwrap_or("(no entries)")
+    ));
+    lines.join("\n")
+}
+
+fn describe_problem(problem: &ChainProblem) -> String {
+    match problem {
+        ChainProblem::Unsealed { entry } => {
+            format!("entry {entry}: not on the chain (inserted outside helm)")
+        }
+        ChainProblem::Rewritten { entry } => {
+            format!("entry {entry}: contents do not match its hash (rewritten)")
+        }
+        ChainProblem::Unlinked { entry } => format!(
+            "entry {entry}: does not follow the entry before it (entries deleted or reordered)"
+        ),
+        ChainProblem::ArtifactAltered { entry, observation } => format!(
+            "entry {entry}, observation {observation}: payload does not match its hash (altered)"
+        ),
+        ChainProblem::ArtifactMissing { entry, observation } => {
+            format!("entry {entry}, observation {observation}: payload is missing")
+        }
+        ChainProblem::Truncated { head } => format!(
+            "logbook does not end at its recorded head {} (entries deleted from the end)",
+            head.as_deref().unwrap_or("(none)")
+        ),
+        ChainProblem::Unsigned { entry, signer } => {
+            format!("entry {entry}: not signed by {signer}")
+        }
+        ChainProblem::MissingSignature { entry, signer } => {
+            format!("entry {entry}: not signed, though {signer} has a key (signature stripped)")
+        }
+        ChainProblem::BadSignature { entry, signer } => {
+            format!("entry {entry}: signature is not {signer}'s over this entry (mis-signed)")
+        }
+        ChainProblem::UnknownSigner { entry, signer } => {
+            format!("entry {entry}: signed, but {signer} has no public key to check it against")
+        }
+    }
+}
+
+fn render_entry(number: usize, entry: &LogbookEntry) -> String {
+    let mut lines = entry_lines(number, entry);
+    if let Some(approval) = describe_approval(entry) {
+        lines.push(format!("   {approval}"));
+    }
+    lines.push(String::new());
+    let observations = &entry.bearing.observations;
+    if observations.is_empty() {
+        lines.push("Bearing: no observations".to_string());
+    } else {
+        lines.push(format!("Bearing: {} observation(s)", observations.len()));
+    }
+    for (i, o) in observations.iter().enumerate() {
+        lines.extend(
+            describe_observation(i + 1, o)
+                .iter()
+                .map(|l| format!("  {l}")),
+        );
+    }
+    lines.join("\n")
+}
+
+/// An observation's target and when it was made, then its payload summarized.
+fn describe_observation(k: usize, observation: &Observation) -> Vec<String> {
+    let mut lines = vec![format!(
+        "{k}. {} — observed {}",
+        describe_observe(&observation.target),
+        observation.observed_at.strftime("%Y-%m-%dT%H:%M:%SZ")
+    )];
+    lines.extend(
+        describe_payload(&observation.payload)
+            .into_iter()
+            .map(|l| format!("   {l}")),
+    );
+    lines
+}
+
+/// A short summary of what a payload holds: one line, or one per file read.
+fn describe_payload(payload: &Payload) -> Vec<String> {
+    match payload {
+        Payload::FileContents { contents } => contents
+            .iter()
+            .map(|f| format!("{}: {}", f.path.display(), describe_content(&f.content)))
+            .collect(),
+        Payload::DirectoryTree { listings } => vec![describe_listings(listings)],
+        Payload::RustProject { listings, contents } => vec![format!(
+            "{}; {} document(s) read",
+            describe_listings(listings),
+            contents.len()
+        )],
+        Payload::GitHubPullRequest(pr) => {
+            let mut line = pr
+                .summary
+                .as_ref()
+                .map(|s| {
+                    let head = s
+                        .head_sha
+                        .as_deref()
+                        .map(|sha| format!(", head {sha}"))
+                        .unwrap_or_default();
+                    format!("\"{}\" [{}] by {}{head}; ", s.title, s.state, s.author)
+                })
+                .unwrap_or_default();
+            let failing = pr
+                .checks
+                .iter()
+                .filter(|c| c.conclusion.as_deref() == Some("failure"))
+                .count();
+            line = format!(
+                "{line}{} file(s), {} check(s) ({failing} failing), {} comment(s), \
+                 {} review comment(s), {}",
+                pr.files.len(),
+                pr.checks.len(),
+                pr.comments.len(),
+                pr.reviews.len(),
+                pr.diff.as_ref().map_or_else(
+                    || "no diff".to_string(),
+                    |d| format!("{}-line diff", d.lines().count())
+                ),
+            );
+            vec![line]
+        }
+        Payload::GitHubIssue(issue) => {
+            let head = issue
+                .summary
+                .as_ref()
+                .map(|s| format!("\"{}\" [{}] by {}; ", s.title, s.state, s.author))
+                .unwrap_or_default();
+            vec![format!("{head}{} comment(s)", issue.comments.len())]
+        }
+        Payload::GitHubRepository(repo) => vec![format!(
+            "{} open issue(s), {} open PR(s)",
+            repo.issues.len(),
+            repo.pull_requests.len()
+        )],
+        Payload::Hold { hash } => vec![format!("held as artifact {hash}")],
+    }
+}
+
+fn describe_content(content: &FileContent) -> String {
+    match content {
+        FileContent::Text { content } => format!("{} line(s)", content.lines().count()),
+        FileContent::Binary { size_bytes } => format!("binary, {size_bytes} bytes"),
+        FileContent::Error { message } => format!("unreadable: {message}"),
+    }
+}
+
+fn describe_listings(listings: &[DirectoryListing]) -> String {
+    let entries: usize = listings.iter().map(|l| l.entries.len()).sum();
+    format!("{} directory(ies), {entries} entries", listings.len())
+}
+
+fn render_markdown(voyage: &Voyage, entries: &[LogbookEntry]) -> String {
+    let mut blocks = vec![format!("# Voyage: {}", voyage.intent)];
+    if let Some(ended) = describe_ended(&voyage.status) {
+        blocks.push(ended);
+    }
+    if entries.is_empty() {
+        blocks.push("_No entries._".to_string());
+    }
+
+    for (i, entry) in entries.iter().enumerate() {
+        let (kind, what) = headline(entry);
+        blocks.push(format!("## {kind} {}: {what}", i + 1));
+        blocks.push(describe_provenance(entry));
+        blocks.push(
+            entry
+                .bearing
+                .summary
+                .lines()
+                .map(|l| format!("> {l}"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+        blocks.push(format!("Bearing: {}", bearing_targets(entry)));
+        if let Some(approval) = describe_approval(entry) {
+            blocks.push(approval);
+        }
+    }
+    blocks.join("\n\n")
+}
+
+/// The entry's kind and what happened, e.g. `("Steer", "create PR #45 (…)")`.
+fn headline(entry: &LogbookEntry) -> (&'static str, String) {
+    match &entry.kind {
+        EntryKind::Steer { steer, outcome } => ("Steer", describe_steer(steer, outcome.as_ref())),
+        EntryKind::Log(status) => ("Log", status.clone()),
+    }
+}
+
+fn describe_provenance(entry: &LogbookEntry) -> String {
+    format!(
+        "{} as {} ({}), {}",
+        entry.identity,
+        entry.role,
+        entry.method,
+        entry.recorded_at.strftime("%Y-%m-%dT%H:%M:%SZ")
+    )
+}
+
+fn describe_approval(entry: &LogbookEntry) -> Option<String> {
+    entry.approval.as_ref().map(|a| {
+        format!(
+            "approved by {} as {} ({}): {}",
+            a.provenance.identity, a.provenance.role, a.provenance.method, a.summary
+        )
+    })
+}
+
+fn describe_ended(status: &VoyageStatus) -> Option<String> {
+    match status {
+        VoyageStatus::Active => None,
+        VoyageStatus::Ended {
+            ended_at,
+            status: Some(status),
+        } => Some(format!("Ended {ended_at}: {status}")),
+        VoyageStatus::Ended {
+            ended_at,
+            status: None,
+        } => Some(format!("Ended {ended_at}")),
+    }
+}
+
+/// What the entry's bearing was sealed from, e.g. `issue #42; file src/widget.rs`.
+fn bearing_targets(entry: &LogbookEntry) -> String {
+    if entry.bearing.observations.is_empty() {
+        return "(none)".to_string();
+    }
+    entry
+        .bearing
+        .observations
+        .iter()
+        .map(|o| describe_observe(&o.target))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use jiff::Timestamp;
+    use uuid::Uuid;
+
+    use crate::model::{
+        Approval, Bearing, CommentTarget, FileContents, GitHubSummary, IssuePayload, Observation,
+        Observe, Payload, Provenance, Steer, SteerOutcome,
+    };
+
+    fn voyage() -> Voyage {
+        Voyage {
+            id: Uuid::nil(),
+            intent: "Advance #42: fix widget crash".into(),
+            created_at: Timestamp::UNIX_EPOCH,
+            status: VoyageStatus::Active,
+        }
+    }
+
+    fn entries() -> Vec<LogbookEntry> {
+        let observation = Observation {
+            target: Observe::GitHubIssue { number: 42 },
+            payload: Payload::GitHubIssue(Box::new(IssuePayload {
+                summary: None,
+                comments: vec![],
+            })),
+            observed_at: Timestamp::UNIX_EPOCH,
+        };
+        vec![
+            LogbookEntry {
+                bearing: Bearing {
+                    observations: vec![observation],
+                    summary: "Plan is ready\nTwo steps.".into(),
+                },
+                identity: "john-agent".into(),
+                role: "coder".into(),
+                method: "conversation".into(),
+                recorded_at: Timestamp::UNIX_EPOCH,
+                kind: EntryKind::Steer {
+                    steer: Steer::Comment {
+                        number: 42,
+                        body: "Here's my plan.".into(),
+                        target: CommentTarget::Issue,
+                    },
+                    outcome: None,
+                },
+                approval: Some(Approval {
+                    provenance: Provenance {
+                        identity: "dyreby".into(),
+                        role: "reviewer".into(),
+                        method: "human".into(),
+                    },
+                    summary: "Plan is sound".into(),
+                    approved_at: Timestamp::UNIX_EPOCH,
+                }),
+            },
+            LogbookEntry {
+                bearing: Bearing {
+                    observations: vec![],
+                    summary: "Waiting on review".into(),
+                },
+                identity: "dyreby".into(),
+                role: "planner".into(),
+                method: "conversation".into(),
+                recorded_at: Timestamp::UNIX_EPOCH,
+                kind: EntryKind::Log("waiting".into()),
+                approval: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn text_tells_the_story() {
+        assert_eq!(
+            render_text(&voyage(), &entries()),
+            "Voyage: Advance #42: fix widget crash\n\
+             \n\
+             ── Steer 1 ── comment on issue #42\n   \
+             john-agent as coder (conversation), 1970-01-01T00:00:00Z\n   \
+             Plan is ready\n   \
+             Two steps.\n   \
+             bearing: issue #42\n   \
+             approved by dyreby as reviewer (human): Plan is sound\n\
+             ── Log 2 ── waiting\n   \
+             dyreby as planner (conversation), 1970-01-01T00:00:00Z\n   \
+             Waiting on review\n   \
+             bearing: (none)"
+        );
+    }
+
+    #[test]
+    fn markdown_quotes_summaries_under_entry_headings() {
+        let markdown = render_markdown(&voyage(), &entries());
+        assert!(markdown.starts_with("# Voyage: Advance #42: fix widget crash\n\n"));
+        assert!(markdown.contains("## Steer 1: comment on issue #42\n\n"));
+        assert!(markdown.contains("> Plan is ready\n> Two steps.\n\nBearing: issue #42"));
+        assert!(markdown.contains("## Log 2: waiting"));
+    }
+
+    #[test]
+    fn entry_lists_its_bearing_observations() {
+        assert_eq!(
+            render_entry(1, &entries()[0]),
+            "── Steer 1 ── comment on issue #42\n   \
+             john-agent as coder (conversation), 1970-01-01T00:00:00Z\n   \
+             Plan is ready\n   \
+             Two steps.\n   \
+             approved by dyreby as reviewer (human): Plan is sound\n\
+             \n\
+             Bearing: 1 observation(s)\n  \
+             1. issue #42 — observed 1970-01-01T00:00:00Z\n     \
+             0 comment(s)"
+        );
+        assert!(render_entry(2, &entries()[1]).ends_with("Bearing: no observations"));
+    }
+
+    #[test]
+    fn incomplete_steers_say_so() {
+        let mut entry = entries().remove(0);
+        entry.kind = EntryKind::Steer {
+            steer: Steer::CreateIssue {
+                title: "Widget crash".into(),
+                body: String::new(),
+                labels: vec![],
+                assignees: vec![],
+                milestone: None,
+            },
+            outcome: Some(SteerOutcome::Incomplete {
+                landed: None,
+                error: "gh printed no URL".into(),
+            }),
+        };
+        assert!(
+            render_entry(1, &entry)
+                .starts_with("── Steer 1 ── create issue \"Widget crash\" (incomplete)\n")
+        );
+    }
+
+    #[test]
+    fn payload_summaries_count_what_was_seen() {
+        let files = Payload::FileContents {
+            contents: vec![
+                FileContents {
+                    path: "src/main.rs".into(),
+                    content: FileContent::Text {
+                        content: "fn main() {\n}\n".into(),
+                    },
+                },
+                FileContents {
+                    path: "logo.png".into(),
+                    content: FileContent::Binary { size_bytes: 512 },
+                },
+            ],
+        };
+        assert_eq!(
+            describe_payload(&files),
+            ["src/main.rs: 2 line(s)", "logo.png: binary, 512 bytes"]
+        );
+
+        let issue = Payload::GitHubIssue(Box::new(IssuePayload {
+            summary: Some(GitHubSummary {
+                title: "Widget crashes".into(),
+                number: 42,
+                state: "OPEN".into(),
+                author: "dyreby".into(),
+                labels: vec![],
+                assignees: vec![],
+                head_branch: None,
+                base_branch: None,
+                head_sha: None,
+                body: None,
+            }),
+            comments: vec![],
+        }));
+        assert_eq!(
+            describe_payload(&issue),
+            ["\"Widget crashes\" [OPEN] by dyreby; 0 comment(s)"]
+        );
+    }
+
+    #[test]
+    fn verify_report_lists_problems_or_the_count() {
+        let intact = ChainReport {
+            entries: 3,
+            head: Some("ab12".into()),
+            signed: 1,
+            problems: vec![
+                ChainProblem::Unsigned {
+                    entry: 1,
+                    signer: "alice".into(),
+                },
+                ChainProblem::Unsigned {
+                    entry: 3,
+                    signer: "bob".into(),
+                },
+            ],
+        };
+        assert_eq!(
+            render_report(&intact, false),
+            "unsigned: entries 1 (alice), 3 (bob)\nVerified 3 entries, 1 signed\nHead: ab12"
+        );
+        assert_eq!(failures(&intact, true), 2);
+
+        let broken = ChainReport {
+            entries: 2,
+            head: Some("ab12".into()),
+            signed: 0,
+            problems: vec![
+                ChainProblem::Rewritten { entry: 1 },
+                ChainProblem::ArtifactAltered {
+                    entry: 2,
+                    observation: 1,
+                },
+            ],
+        };
+        assert_eq!(
+            render_report(&broken, false),
+            "entry 1: contents do not match its hash (rewritten)\n\
+             entry 2, observation 1: payload does not match its hash (altered)\n\
+             Head: ab12"
+        );
+    }
+
+    #[test]
+    fn empty_logbook_says_so() {
+        assert!(render_text(&voyage(), &[]).ends_with("No entries"));
+    }
+}
@@ -63,7 +63,7 @@ pub(super) fn cmd_observe(
         Some(path) => {
             fs::write(&path, &json)
                 .map_err(|e| format!("failed to write {}: {e}", path.display()))?;
-            let summary = describe_observe_target(target);
+            let summary = describe_observe(&observe);
             eprintln!("Observed {summary} → {}", path.display());
         }
         None => {
@@ -75,15 +75,19 @@ pub(super) fn cmd_observe(
 }
 
 /// Short human-readable description of what was observed.
-fn describe_observe_target(target: &ObserveTarget) -> String {
-    match target {
-        ObserveTarget::FileContents { read } => format!("{} file(s)", read.len()),
-        ObserveTarget::DirectoryTree { root, .. } => {
-            format!("directory tree at {}", root.display())
+pub(super) fn describe_observe(observe: &Observe) -> String {
+    match observe {
+        Observe::FileContents { paths } => {
+            let paths: Vec<_> = paths.iter().map(|p| p.display().to_string()).collect();
+            match paths.as_slice() {
+                [path] => format!("file {path}"),
+                _ => format!("files {}", paths.join(", ")),
+            }
         }
-        ObserveTarget::RustProject { path } => format!("Rust project at {}", path.display()),
-        ObserveTarget::GitHubPullRequest { number } => format!("PR #{number}"),
-        ObserveTarget::GitHubIssue { number } => format!("issue #{number}"),
-        ObserveTarget::GitHubRepository => "repository".to_string(),
+        Observe::DirectoryTree { root, .. } => format!("directory tree at {}", root.display()),
+        Observe::RustProject { root } => format!("Rust project at {}", root.display()),
+        Observe::GitHubPullRequest { number } => format!("PR #{number}"),
+        Observe::GitHubIssue { number } => format!("issue #{number}"),
+        Observe::GitHubRepository => "repository".to_string(),
     }
 }
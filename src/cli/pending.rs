@@ -0,0 +1,125 @@
+//! Pending steer commands: list, resolve.
+//!
+//! A steer is journaled before its action runs and recorded once it finishes.
+//! One left performing means helm was interrupted in between, so nobody knows
+//! whether the action happened. These commands let someone check and settle it.
+//! Proposals awaiting approval are pending too, and are listed alongside.
+
+use clap::{ArgGroup, Subcommand};
+
+use crate::{
+    model::{PendingStatus, SteerOutcome, Voyage},
+    storage::Storage,
+};
+
+#[derive(Debug, Subcommand)]
+pub enum PendingCommand {
+    /// List steers that were started but never recorded, and proposals awaiting approval.
+    ///
+    /// A performing steer was interrupted after its bearing was sealed;
+    /// its action may or may not have happened.
+    List {
+        /// Voyage ID: full UUID or unambiguous prefix (e.g. `a3b`).
+        #[arg(long)]
+        voyage: String,
+    },
+
+    /// Resolve a pending steer once you've checked whether its action happened.
+    ///
+    /// `--performed` records it in the logbook with the bearing it sealed.
+    /// `--abandoned` drops it and returns its observations to the slate;
+    /// this is also how a proposal is rejected.
+    #[command(group(
+        ArgGroup::new("resolution")
+            .required(true)
+            .args(["performed", "abandoned"])
+    ))]
+    Resolve {
+        /// Voyage ID: full UUID or unambiguous prefix (e.g. `a3b`).
+        #[arg(long)]
+        voyage: String,
+
+        /// Pending steer ID, as shown by `helm pending list`.
+        id: i64,
+
+        /// The action took effect: record it.
+        #[arg(long)]
+        performed: bool,
+
+        /// The action did not take effect: drop it.
+        #[arg(long)]
+        abandoned: bool,
+    },
+}
+
+pub(super) fn cmd_list(storage: &Storage, voyage: &Voyage) -> Result<(), String> {
+    let pending = storage
+        .load_pending_steers(voyage.id)
+        .map_err(|e| format!("failed to load pending steers: {e}"))?;
+
+    if pending.is_empty() {
+        println!("No pending steers");
+        return Ok(());
+    }
+
+    for p in &pending {
+        let status = match p.status {
+            PendingStatus::Proposed => "proposed",
+            PendingStatus::Performing => "performing",
+        };
+        println!(
+            "{}  [{status}]  {}  {} ({}, {})  {}  [{} observations]\n    {}",
+            p.id,
+            p.started_at,
+            p.provenance.identity,
+            p.provenance.role,
+            p.provenance.method,
+            super::steer::describe_steer(&p.steer, None),
+            p.observation_count,
+            p.summary,
+        );
+        if let Some(approval) = &p.approval {
+            println!(
+                "    approved by {} ({}, {}): {}",
+                approval.provenance.identity,
+                approval.provenance.role,
+                approval.provenance.method,
+                approval.summary,
+            );
+        }
+    }
+
+    Ok(())
+}
+
+pub(super) fn cmd_resolve(
+    storage: &Storage,
+    voyage: &Voyage,
+    id: i64,
+    performed: bool,
+) -> Result<(), String> {
+    if performed {
+        let pending = storage
+            .load_pending_steers(voyage.id)
+            .map_err(|e| format!("failed to load pending steers: {e}"))?;
+        if pending
+            .iter()
+            .any(|p| p.id == id && p.status == PendingStatus::Proposed)
+        {
+            return Err(format!(
+                "steer {id} is a proposal and was never performed — \
+                 approve it with `helm steer ... approve {id}`, or reject it with --abandoned"
+            ));
+        }
+        storage
+            .complete_steer(voyage.id, id, Some(&SteerOutcome::Reconciled))
+            .map_err(|e| format!("failed to record pending steer: {e}"))?;
+        eprintln!("Recorded pending steer {id}");
+    } else {
+        storage
+            .abandon_steer(voyage.id, id)
+            .map_err(|e| format!("failed to abandon pending steer: {e}"))?;
+        eprintln!("Abandoned pending steer {id}; its observations are back on the slate");
+    }
+    Ok(())
+}
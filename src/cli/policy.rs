@@ -0,0 +1,84 @@
+//! Policy enforcement for steers, and the denials it leaves behind.
+//!
+//! Steers are checked against `~/.helm/policy.toml` just before they would
+//! be journaled. A refused steer touches neither GitHub nor the slate;
+//! it is recorded as a denial so whoever shaped the envelope can see
+//! what pushed against it.
+
+use std::path::Path;
+
+use crate::{
+    model::{Provenance, Steer, Voyage},
+    policy, steer,
+    storage::Storage,
+};
+
+/// Check a steer against the policy, recording a denial if it is refused.
+///
+/// `actor` is one identity the steer must fit: the steerer, or for a
+/// proposal, its proposer and its approver in turn. `gh_config` is the
+/// identity the steer runs as, used to find the repository when the policy
+/// limits repositories.
+pub(super) fn enforce(
+    storage: &Storage,
+    voyage: &Voyage,
+    steer_action: &Steer,
+    summary: &str,
+    actor: &Provenance,
+    gh_config: &Path,
+) -> Result<(), String> {
+    let Some(policy) = policy::load_policy()? else {
+        return Ok(());
+    };
+
+    // An unknown repository only fails the rules that limit repositories;
+    // others may still allow the steer.
+    let (repo, repo_error) = if policy.needs_repo(&actor.identity, &actor.role) {
+        match steer::current_repo(gh_config) {
+            Ok(repo) => (Some(repo), None),
+            Err(e) => (None, Some(e)),
+        }
+    } else {
+        (None, None)
+    };
+
+    let Err(mut reason) = policy.check(steer_action, &actor.identity, &actor.role, repo.as_deref())
+    else {
+        return Ok(());
+    };
+    if let Some(e) = repo_error {
+        reason = format!("{reason} (could not determine the repository: {e})");
+    }
+
+    storage
+        .record_denial(voyage.id, steer_action, summary, actor, &reason)
+        .map_err(|e| format!("denied: {reason}\nfailed to record denial: {e}"))?;
+    Err(format!("denied: {reason}"))
+}
+
+/// List the steers the policy refused in a voyage, oldest first.
+pub(super) fn cmd_denials(storage: &Storage, voyage: &Voyage) -> Result<(), String> {
+    let denials = storage
+        .load_denials(voyage.id)
+        .map_err(|e| format!("failed to load denials: {e}"))?;
+
+    if denials.is_empty() {
+        println!("No denials");
+        return Ok(());
+    }
+
+    for d in &denials {
+        println!(
+            "{}  {} ({}, {})  {}\n    {}\n    summary: {}",
+            d.denied_at,
+            d.provenance.identity,
+            d.provenance.role,
+            d.provenance.method,
+            super::steer::describe_steer(&d.steer, None),
+            d.reason,
+            d.summary,
+        );
+    }
+
+    Ok(())
+}
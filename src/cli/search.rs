@@ -0,0 +1,165 @@
+//! Search command: find text across every voyage.
+//!
+//! Hits point back at where the text lives — an intent, an ended status,
+//! a logbook entry, or an observation sealed into one — so `helm logbook entry`
+//! can open it.
+
+use clap::ValueEnum;
+use jiff::{Timestamp, civil::Date, tz::TimeZone};
+
+use crate::{
+    model::VoyageStatus,
+    storage::{HitSource, SearchFilter, SearchHit, Storage},
+};
+
+/// Voyage statuses accepted by `search --status`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum StatusFilter {
+    Active,
+    Ended,
+}
+
+/// Parse `--since`: a timestamp, or a date meaning the start of that day (UTC).
+pub(super) fn parse_since(s: &str) -> Result<Timestamp, String> {
+    parse_instant(s, false)
+}
+
+/// Parse `--until`: a timestamp, or a date meaning the end of that day (UTC).
+pub(super) fn parse_until(s: &str) -> Result<Timestamp, String> {
+    parse_instant(s, true)
+}
+
+fn parse_instant(s: &str, end_of_day: bool) -> Result<Timestamp, String> {
+    if let Ok(timestamp) = s.parse::<Timestamp>() {
+        return Ok(timestamp);
+    }
+    let date: Date = s.parse().map_err(|_| {
+        format!("expected a date (2026-10-17) or timestamp (2026-10-17T09:00:00Z), got {s:?}")
+    })?;
+    let date = if end_of_day {
+        date.tomorrow().map_err(|e| e.to_string())?
+    } else {
+        date
+    };
+    date.to_zoned(TimeZone::UTC)
+        .map(|zoned| zoned.timestamp())
+        .map_err(|e| e.to_string())
+}
+
+pub(super) fn cmd_search(
+    storage: &Storage,
+    query: &str,
+    identity: Option<String>,
+    since: Option<Timestamp>,
+    until: Option<Timestamp>,
+    status: Option<StatusFilter>,
+    limit: usize,
+) -> Result<(), String> {
+    let filter = SearchFilter {
+        identity,
+        since,
+        until,
+        ended: status.map(|s| matches!(s, StatusFilter::Ended)),
+    };
+    let hits = storage
+        .search(query, &filter, limit)
+        .map_err(|e| format!("failed to search voyages: {e}"))?;
+
+    if hits.is_empty() {
+        println!("No matches");
+        return Ok(());
+    }
+    println!("{}", render_hits(&hits));
+    Ok(())
+}
+
+fn render_hits(hits: &[SearchHit]) -> String {
+    hits.iter()
+        .map(|hit| {
+            let short_id = &hit.voyage.id.to_string()[..8];
+            let status = match hit.voyage.status {
+                VoyageStatus::Active => "active",
+                VoyageStatus::Ended { .. } => "ended",
+            };
+            let snippet = hit.snippet.split_whitespace().collect::<Vec<_>>().join(" ");
+            format!(
+                "{short_id}  [{status}]  {}\n   {} — {}\n   {snippet}",
+                hit.voyage.intent,
+                describe_source(hit.source),
+                describe_provenance(hit),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn describe_source(source: HitSource) -> String {
+    match source {
+        HitSource::Intent => "intent".to_string(),
+        HitSource::Ended => "ended status".to_string(),
+        HitSource::Entry(entry) => format!("entry {entry}"),
+        HitSource::Observation { entry, observation } => {
+            format!("entry {entry}, observation {observation}")
+        }
+    }
+}
+
+fn describe_provenance(hit: &SearchHit) -> String {
+    let at = hit.at.strftime("%Y-%m-%dT%H:%M:%SZ");
+    match &hit.identity {
+        Some(identity) => format!("{identity}, {at}"),
+        None => at.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use uuid::Uuid;
+
+    use crate::model::Voyage;
+
+    #[test]
+    fn dates_cover_whole_days() {
+        assert_eq!(
+            parse_since("2026-10-17").unwrap().to_string(),
+            "2026-10-17T00:00:00Z"
+        );
+        assert_eq!(
+            parse_until("2026-10-17").unwrap().to_string(),
+            "2026-10-18T00:00:00Z"
+        );
+        assert_eq!(
+            parse_until("2026-10-17T09:30:00Z").unwrap().to_string(),
+            "2026-10-17T09:30:00Z"
+        );
+        assert!(parse_since("last week").is_err());
+    }
+
+    #[test]
+    fn hits_point_at_where_the_text_lives() {
+        let hit = SearchHit {
+            voyage: Voyage {
+                id: Uuid::nil(),
+                intent: "Fix the widget crash".into(),
+                created_at: Timestamp::UNIX_EPOCH,
+                status: VoyageStatus::Active,
+            },
+            source: HitSource::Observation {
+                entry: 3,
+                observation: 2,
+            },
+            identity: Some("alice".into()),
+            at: Timestamp::UNIX_EPOCH,
+            snippet: "…the [gyroscope]\n  overflows…".into(),
+            rank: -1.0,
+        };
+        assert_eq!(
+            render_hits(&[hit]),
+            "00000000  [active]  Fix the widget crash\n   \
+             entry 3, observation 2 — alice, 1970-01-01T00:00:00Z\n   \
+             …the [gyroscope] overflows…"
+        );
+    }
+}
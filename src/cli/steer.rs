@@ -0,0 +1,693 @@
+//! Steer command: seal the slate, perform an action, record one logbook entry.
+//! Or seal it into a proposal, and later approve and perform that proposal.
+//! Either way, the policy is checked before anything is journaled or performed,
+//! and configured hooks run before and after the action.
+//!
+//! Each steer subcommand has an `Args` struct that builds its typed `Steer`.
+//! Argument structs are grouped by subject, mirroring `crate::steer`.
+
+mod checks;
+mod comment;
+mod conversation;
+mod issue;
+mod pull_request;
+mod release;
+mod review;
+mod template;
+mod text;
+mod triage;
+
+use clap::Subcommand;
+use jiff::Timestamp;
+use serde::Serialize;
+
+use crate::{
+    hooks::{self, Event, HookAction, HookInput, Hooks},
+    model::{
+        Approval, Bearing, CommentTarget, Observation, PendingStatus, Provenance, ReactionTarget,
+        Steer, SteerOutcome, Subject, Voyage,
+    },
+    steer::{self, Failure},
+    storage::Storage,
+};
+
+use checks::{DispatchWorkflowArgs, RerunChecksArgs};
+use comment::CommentArgs;
+use conversation::{LockArgs, ReactArgs};
+use issue::{CloseIssueArgs, CreateIssueArgs, EditArgs};
+use pull_request::{
+    ClosePullRequestArgs, CreatePullRequestArgs, MergePullRequestArgs, ReadyPullRequestArgs,
+    UpdateBranchArgs,
+};
+use release::CreateReleaseArgs;
+use review::{RequestReviewArgs, ResolveThreadArgs, SubmitReviewArgs};
+use triage::{AssignArgs, LabelArgs, MilestoneArgs};
+
+/// Steer subcommands.
+#[derive(Debug, Subcommand)]
+pub enum SteerAction {
+    /// Comment on an issue, PR, or inline review thread.
+    Comment(CommentArgs),
+
+    /// Create an issue.
+    ///
+    /// The new issue's number and URL are recorded in the logbook entry.
+    CreateIssue(CreateIssueArgs),
+
+    /// Edit an issue's title, body, or labels.
+    ///
+    /// The previous and new values are recorded in the logbook entry.
+    EditIssue(EditArgs),
+
+    /// Add and remove labels on an issue or PR.
+    ///
+    /// The labels, assignees, and milestone before and after are recorded in the logbook entry.
+    Label(LabelArgs),
+
+    /// Assign and unassign users on an issue or PR.
+    ///
+    /// The labels, assignees, and milestone before and after are recorded in the logbook entry.
+    Assign(AssignArgs),
+
+    /// Set or clear the milestone on an issue or PR.
+    ///
+    /// The labels, assignees, and milestone before and after are recorded in the logbook entry.
+    Milestone(MilestoneArgs),
+
+    /// Close an issue, or reopen it with `--reopen`.
+    CloseIssue(CloseIssueArgs),
+
+    /// Open a pull request.
+    ///
+    /// The new PR's number, URL, and head/base commits are recorded in the logbook entry.
+    #[command(name = "create-pr")]
+    CreatePullRequest(CreatePullRequestArgs),
+
+    /// Edit a pull request's title, body, or labels.
+    ///
+    /// The previous and new values are recorded in the logbook entry.
+    #[command(name = "edit-pr")]
+    EditPullRequest(EditArgs),
+
+    /// Close a pull request without merging, or reopen it with `--reopen`.
+    #[command(name = "close-pr")]
+    ClosePullRequest(ClosePullRequestArgs),
+
+    /// Mark a draft pull request ready for review, or convert it to a draft with `--draft`.
+    #[command(name = "ready-pr")]
+    ReadyPullRequest(ReadyPullRequestArgs),
+
+    /// Bring a pull request's head branch up to date with its base.
+    ///
+    /// Merges the base in, or rebases onto it with `--rebase`.
+    /// The new head commit is recorded in the logbook entry.
+    UpdateBranch(UpdateBranchArgs),
+
+    /// Request reviewers on a pull request, or withdraw requests with `--remove`.
+    ///
+    /// The resulting set of requested reviewers is recorded in the logbook entry.
+    RequestReview(RequestReviewArgs),
+
+    /// Submit a review on a pull request: verdict, summary, and inline comments.
+    ///
+    /// Posts as one review; the review ID is recorded in the logbook entry.
+    /// To reply to an existing inline thread, use `comment --pr N --reply-to ID`.
+    SubmitReview(SubmitReviewArgs),
+
+    /// Resolve the review thread holding a review comment, or unresolve it with `--unresolve`.
+    ///
+    /// The thread's node ID is recorded in the logbook entry.
+    ResolveThread(ResolveThreadArgs),
+
+    /// React to a comment on an issue or PR, or remove your reaction with `--remove`.
+    ///
+    /// The reaction's ID is recorded in the logbook entry.
+    React(ReactArgs),
+
+    /// Lock the conversation on an issue or PR, or unlock it with `--unlock`.
+    Lock(LockArgs),
+
+    /// Re-run the failed jobs of every failed workflow run on a pull request's head.
+    ///
+    /// The head commit and the re-run workflow run IDs are recorded in the logbook entry.
+    RerunChecks(RerunChecksArgs),
+
+    /// Trigger a workflow's `workflow_dispatch` event.
+    DispatchWorkflow(DispatchWorkflowArgs),
+
+    /// Create a release, tagging `--target` if the tag doesn't exist yet.
+    ///
+    /// The notes are recorded with the steer.
+    /// The release URL is recorded in the logbook entry.
+    CreateRelease(CreateReleaseArgs),
+
+    /// Merge a pull request.
+    ///
+    /// Refuses to merge if the PR head has moved since it was observed.
+    /// The expected head comes from `--expected-head`, or from the PR's
+    /// observation on the slate — one of the two is required.
+    /// The merge commit is recorded in the logbook entry.
+    #[command(name = "merge-pr")]
+    MergePullRequest(MergePullRequestArgs),
+}
+
+/// Everything `helm steer` accepts after its flags: a steer action, or approval of a proposal.
+#[derive(Debug, Subcommand)]
+pub enum SteerCommand {
+    #[command(flatten)]
+    Action(SteerAction),
+
+    /// Approve a proposed steer and perform it.
+    ///
+    /// Runs under the proposer's GitHub identity. The logbook entry records
+    /// the proposer as the actor and the approver (`--as`, `--role`,
+    /// `--method`, `--summary`) as co-signer. The approver must be a
+    /// different identity from the proposer.
+    Approve {
+        /// Pending steer ID, as shown by `helm pending list`.
+        id: i64,
+    },
+}
+
+/// What `helm steer` does with the steer it builds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Mode {
+    /// Seal, perform, and record.
+    Perform,
+
+    /// Print what would happen; touch nothing.
+    DryRun,
+
+    /// Seal into a proposal for another identity to approve; don't perform.
+    Propose,
+}
+
+impl Mode {
+    /// From `--dry-run` and `--propose`, which clap keeps mutually exclusive.
+    pub(super) fn from_flags(dry_run: bool, propose: bool) -> Self {
+        if dry_run {
+            Self::DryRun
+        } else if propose {
+            Self::Propose
+        } else {
+            Self::Perform
+        }
+    }
+}
+
+pub(super) fn cmd_steer(
+    storage: &Storage,
+    voyage: &Voyage,
+    provenance: &Provenance,
+    summary: &str,
+    action: &SteerAction,
+    mode: Mode,
+    template: bool,
+) -> Result<(), String> {
+    // 1. Build the typed steer action from CLI args and the slate about to be sealed.
+    //    Templates render now, so every mode sees — and seals — the final text.
+    let slate = storage
+        .load_slate(voyage.id)
+        .map_err(|e| format!("failed to load slate: {e}"))?;
+    let mut steer_action = build_steer_action(action, &slate)?;
+    if template {
+        let context = template::Context::load(storage, voyage, &slate)?;
+        template::render(&mut steer_action, &context)?;
+    }
+
+    match mode {
+        Mode::DryRun => return print_dry_run(&steer_action, provenance, summary, slate),
+        Mode::Propose => {
+            let id = storage
+                .propose_steer(voyage.id, &steer_action, summary, provenance)
+                .map_err(|e| format!("failed to record proposal: {e}"))?;
+            eprintln!(
+                "Proposed: {} — approve with: helm steer --voyage {} ... approve {id}",
+                describe_steer(&steer_action, None),
+                &voyage.id.to_string()[..8],
+            );
+            println!("{id}");
+            return Ok(());
+        }
+        Mode::Perform => {}
+    }
+    let gh_config = super::gh_config_dir(&provenance.identity)?;
+    super::policy::enforce(
+        storage,
+        voyage,
+        &steer_action,
+        summary,
+        provenance,
+        &gh_config,
+    )?;
+    let hooks = hooks::load_hooks()?;
+    let mut hook_input = HookInput {
+        event: Event::PreSteer,
+        voyage_id: voyage.id,
+        provenance,
+        summary,
+        action: HookAction::Steer {
+            steer: &steer_action,
+            outcome: None,
+            approval: None,
+        },
+        slate: &slate,
+    };
+    hooks
+        .run(&hook_input)
+        .map_err(|e| format!("steer aborted by pre-steer hook: {e}"))?;
+    storage
+        .check_signer(&provenance.identity)
+        .map_err(|e| format!("steer aborted before acting: {e}"))?;
+
+    // 2. Journal the steer and seal the slate into it — before anything crosses the boundary.
+    let pending_id = storage
+        .begin_steer(voyage.id, &steer_action, summary, provenance)
+        .map_err(|e| format!("failed to journal steer: {e}"))?;
+
+    // 3. Perform the action — mutate collaborative state.
+    //    An action that changed nothing is abandoned: its observations go back
+    //    on the slate. One that changed something is recorded, even if incomplete.
+    let (outcome, failure) = match steer::perform(&steer_action, &gh_config) {
+        Ok(outcome) => (outcome, None),
+        Err(Failure::NotPerformed(e)) => {
+            storage
+                .abandon_steer(voyage.id, pending_id)
+                .map_err(|abandon_err| {
+                    format!("{e}\nfailed to abandon pending steer {pending_id}: {abandon_err}")
+                })?;
+            return Err(e);
+        }
+        Err(Failure::Partial { landed, error }) => incomplete(landed, error),
+    };
+
+    // 4. Record one logbook entry from the journal.
+    record(storage, voyage, pending_id, &steer_action, outcome.as_ref())?;
+    eprintln!(
+        "{}: {}",
+        if failure.is_some() {
+            "Partly steered"
+        } else {
+            "Steered"
+        },
+        describe_steer(&steer_action, outcome.as_ref())
+    );
+
+    hook_input.event = Event::PostSteer;
+    hook_input.action = HookAction::Steer {
+        steer: &steer_action,
+        outcome: outcome.as_ref(),
+        approval: None,
+    };
+    warn_post_hooks(&hooks, &hook_input);
+    print_outcome(outcome.as_ref())?;
+    failure.map_or(Ok(()), Err)
+}
+
+/// Approve a proposal, perform it as its proposer, and record both identities.
+pub(super) fn cmd_approve(
+    storage: &Storage,
+    voyage: &Voyage,
+    approver: &Provenance,
+    summary: &str,
+    id: i64,
+    mode: Mode,
+) -> Result<(), String> {
+    if mode != Mode::Perform {
+        return Err("--dry-run and --propose don't apply to approve".into());
+    }
+    let proposal = storage
+        .load_pending_steers(voyage.id)
+        .map_err(|e| format!("failed to load pending steers: {e}"))?
+        .into_iter()
+        .find(|p| p.id == id && p.status == PendingStatus::Proposed)
+        .ok_or_else(|| format!("no proposal {id} awaiting approval"))?;
+    if proposal.provenance.identity == approver.identity {
+        return Err(format!(
+            "{} proposed steer {id}; a different identity must approve it",
+            approver.identity
+        ));
+    }
+    let gh_config = super::gh_config_dir(&proposal.provenance.identity)?;
+    // The steer runs with the proposer's credentials and the approver answers
+    // for it, so it must fit both envelopes.
+    for actor in [&proposal.provenance, approver] {
+        super::policy::enforce(storage, voyage, &proposal.steer, summary, actor, &gh_config)?;
+    }
+
+    let approval = Approval {
+        provenance: approver.clone(),
+        summary: summary.to_string(),
+        approved_at: Timestamp::now(),
+    };
+    let bearing = storage
+        .load_pending_bearing(voyage.id, id)
+        .map_err(|e| format!("failed to load the proposal's bearing: {e}"))?;
+    let hooks = hooks::load_hooks()?;
+    let mut hook_input = HookInput {
+        event: Event::PreSteer,
+        voyage_id: voyage.id,
+        provenance: &proposal.provenance,
+        summary: &proposal.summary,
+        action: HookAction::Steer {
+            steer: &proposal.steer,
+            outcome: None,
+            approval: Some(&approval),
+        },
+        slate: &bearing,
+    };
+    hooks
+        .run(&hook_input)
+        .map_err(|e| format!("approval aborted by pre-steer hook: {e}"))?;
+    storage
+        .check_signer(&approver.identity)
+        .map_err(|e| format!("approval aborted before acting: {e}"))?;
+
+    storage
+        .approve_steer(voyage.id, id, &approval)
+        .map_err(|e| format!("failed to approve steer: {e}"))?;
+
+    // An action that changed nothing leaves the proposal awaiting approval
+    // again, bearing intact. One that changed something is recorded.
+    let (outcome, failure) = match steer::perform(&proposal.steer, &gh_config) {
+        Ok(outcome) => (outcome, None),
+        Err(Failure::NotPerformed(e)) => {
+            storage
+                .withdraw_approval(voyage.id, id)
+                .map_err(|withdraw_err| {
+                    format!("{e}\nfailed to withdraw approval of steer {id}: {withdraw_err}")
+                })?;
+            return Err(e);
+        }
+        Err(Failure::Partial { landed, error }) => incomplete(landed, error),
+    };
+
+    record(storage, voyage, id, &proposal.steer, outcome.as_ref())?;
+    eprintln!(
+        "{}: {} (proposed by {}, approved by {})",
+        if failure.is_some() {
+            "Partly steered"
+        } else {
+            "Steered"
+        },
+        describe_steer(&proposal.steer, outcome.as_ref()),
+        proposal.provenance.identity,
+        approver.identity,
+    );
+
+    hook_input.event = Event::PostSteer;
+    hook_input.action = HookAction::Steer {
+        steer: &proposal.steer,
+        outcome: outcome.as_ref(),
+        approval: Some(&approval),
+    };
+    warn_post_hooks(&hooks, &hook_input);
+    print_outcome(outcome.as_ref())?;
+    failure.map_or(Ok(()), Err)
+}
+
+/// Run post-hooks, warning about failures — the entry is already recorded.
+pub(super) fn warn_post_hooks(hooks: &Hooks, input: &HookInput<'_>) {
+    if let Err(e) = hooks.run(input) {
+        eprintln!("warning: {e}");
+    }
+}
+
+/// The outcome to record for a steer that landed and then failed,
+/// and the error to report once it's recorded.
+fn incomplete(
+    landed: Option<SteerOutcome>,
+    error: String,
+) -> (Option<SteerOutcome>, Option<String>) {
+    let report =
+        format!("{error}\nThe change reached GitHub, so the steer is recorded as incomplete");
+    let outcome = SteerOutcome::Incomplete {
+        landed: landed.map(Box::new),
+        error,
+    };
+    (Some(outcome), Some(report))
+}
+
+/// Record a performed steer from the journal — one transaction.
+fn record(
+    storage: &Storage,
+    voyage: &Voyage,
+    pending_id: i64,
+    steer_action: &Steer,
+    outcome: Option<&SteerOutcome>,
+) -> Result<(), String> {
+    storage
+        .complete_steer(voyage.id, pending_id, outcome)
+        .map_err(|e| {
+            format!(
+                "{} performed but not recorded: {e}\n\
+                 The steer is left pending; record it with: \
+                 helm pending resolve --voyage {} {pending_id} --performed",
+                describe_steer(steer_action, outcome),
+                &voyage.id.to_string()[..8],
+            )
+        })
+}
+
+/// Print what the action produced so callers can refer to it in later steers.
+fn print_outcome(outcome: Option<&SteerOutcome>) -> Result<(), String> {
+    if let Some(outcome) = outcome {
+        let json = serde_json::to_string_pretty(outcome)
+            .map_err(|e| format!("failed to serialize steer outcome: {e}"))?;
+        println!("{json}");
+    }
+    Ok(())
+}
+
+/// What `--dry-run` prints instead of steering.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DryRun<'a> {
+    steer: &'a Steer,
+    provenance: &'a Provenance,
+    /// Shell-quoted `gh` command lines, in the order they would run.
+    invocations: Vec<String>,
+    bearing: Bearing,
+}
+
+fn print_dry_run(
+    steer_action: &Steer,
+    provenance: &Provenance,
+    summary: &str,
+    slate: Vec<Observation>,
+) -> Result<(), String> {
+    let dry_run = DryRun {
+        steer: steer_action,
+        provenance,
+        invocations: steer::plan(steer_action)
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        bearing: Bearing {
+            observations: slate,
+            summary: summary.to_string(),
+        },
+    };
+    let json = serde_json::to_string_pretty(&dry_run)
+        .map_err(|e| format!("failed to serialize dry run: {e}"))?;
+    println!("{json}");
+
+    eprintln!(
+        "Dry run: would {} — nothing performed or recorded",
+        describe_steer(steer_action, None)
+    );
+    Ok(())
+}
+
+/// Convert CLI steer args to the typed `Steer` model.
+///
+/// `slate` is what the bearing will be sealed from; some actions
+/// (e.g. `merge-pr`) pin themselves to what was observed there.
+fn build_steer_action(action: &SteerAction, slate: &[Observation]) -> Result<Steer, String> {
+    let steer = match action {
+        SteerAction::Comment(args) => args.build()?,
+        SteerAction::CreateIssue(args) => args.build()?,
+        SteerAction::EditIssue(args) => args.build_issue()?,
+        SteerAction::Label(args) => args.build(),
+        SteerAction::Assign(args) => args.build(),
+        SteerAction::Milestone(args) => args.build(),
+        SteerAction::CloseIssue(args) => args.build()?,
+        SteerAction::CreatePullRequest(args) => args.build()?,
+        SteerAction::EditPullRequest(args) => args.build_pull_request()?,
+        SteerAction::ClosePullRequest(args) => args.build()?,
+        SteerAction::ReadyPullRequest(args) => args.build(),
+        SteerAction::UpdateBranch(args) => args.build(),
+        SteerAction::RequestReview(args) => args.build(),
+        SteerAction::SubmitReview(args) => args.build()?,
+        SteerAction::ResolveThread(args) => args.build(),
+        SteerAction::React(args) => args.build()?,
+        SteerAction::Lock(args) => args.build(),
+        SteerAction::RerunChecks(args) => args.build(),
+        SteerAction::DispatchWorkflow(args) => args.build()?,
+        SteerAction::CreateRelease(args) => args.build()?,
+        SteerAction::MergePullRequest(args) => args.build(slate)?,
+    };
+    Ok(steer)
+}
+
+/// Short human-readable description of what was steered.
+// One arm per steer variant (and outcome worth naming), like `steer::perform`.
+#[allow(clippy::too_many_lines)]
+pub(super) fn describe_steer(steer: &Steer, outcome: Option<&SteerOutcome>) -> String {
+    match (steer, outcome) {
+        (_, Some(SteerOutcome::Incomplete { landed, .. })) => {
+            format!("{} (incomplete)", describe_steer(steer, landed.as_deref()))
+        }
+        (Steer::Comment { number, target, .. }, _) => match target {
+            CommentTarget::Issue => format!("comment on issue #{number}"),
+            CommentTarget::PullRequest => format!("comment on PR #{number}"),
+            CommentTarget::ReviewFeedback { comment_id } => {
+                format!("reply to review comment {comment_id} on PR #{number}")
+            }
+        },
+        (Steer::CreateIssue { .. }, Some(SteerOutcome::IssueCreated { number, url })) => {
+            format!("create issue #{number} ({url})")
+        }
+        (Steer::CreateIssue { title, .. }, _) => format!("create issue \"{title}\""),
+        (
+            Steer::CreatePullRequest { .. },
+            Some(SteerOutcome::PullRequestCreated { number, url, .. }),
+        ) => format!("create PR #{number} ({url})"),
+        (Steer::CreatePullRequest { head, base, .. }, _) => {
+            format!("create PR {head} → {base}")
+        }
+        (
+            Steer::MergePullRequest { number, .. },
+            Some(SteerOutcome::PullRequestMerged {
+                merge_commit_sha: Some(sha),
+            }),
+        ) => format!("merge PR #{number} ({sha})"),
+        (Steer::MergePullRequest { number, .. }, _) => format!("merge PR #{number}"),
+        (Steer::EditIssue { number, .. }, _) => format!("edit issue #{number}"),
+        (Steer::EditPullRequest { number, .. }, _) => format!("edit PR #{number}"),
+        (
+            Steer::Label {
+                number, subject, ..
+            },
+            _,
+        ) => format!("label {}", subject_ref(*subject, *number)),
+        (
+            Steer::Assign {
+                number, subject, ..
+            },
+            _,
+        ) => format!("assign {}", subject_ref(*subject, *number)),
+        (
+            Steer::SetMilestone {
+                number,
+                subject,
+                milestone: Some(milestone),
+            },
+            _,
+        ) => format!(
+            "set milestone \"{milestone}\" on {}",
+            subject_ref(*subject, *number)
+        ),
+        (
+            Steer::SetMilestone {
+                number,
+                subject,
+                milestone: None,
+            },
+            _,
+        ) => format!("clear milestone on {}", subject_ref(*subject, *number)),
+        (Steer::CloseIssue { number, .. }, _) => format!("close issue #{number}"),
+        (Steer::ReopenIssue { number, .. }, _) => format!("reopen issue #{number}"),
+        (Steer::ClosePullRequest { number, .. }, _) => format!("close PR #{number}"),
+        (Steer::ReopenPullRequest { number, .. }, _) => format!("reopen PR #{number}"),
+        (Steer::MarkReadyForReview { number }, _) => format!("mark PR #{number} ready for review"),
+        (Steer::ConvertToDraft { number }, _) => format!("convert PR #{number} to draft"),
+        (
+            Steer::UpdateBranch { number, .. },
+            Some(SteerOutcome::BranchUpdated {
+                head_sha: Some(sha),
+            }),
+        ) => format!("update PR #{number} branch ({sha})"),
+        (Steer::UpdateBranch { number, .. }, _) => format!("update PR #{number} branch"),
+        (Steer::RequestReview { number, .. }, _) => format!("request review on PR #{number}"),
+        (Steer::SubmitReview { number, .. }, _) => format!("review PR #{number}"),
+        (Steer::ResolveThread { number, comment_id }, _) => {
+            format!("resolve thread of review comment {comment_id} on PR #{number}")
+        }
+        (Steer::UnresolveThread { number, comment_id }, _) => {
+            format!("unresolve thread of review comment {comment_id} on PR #{number}")
+        }
+        (
+            Steer::React {
+                number,
+                subject,
+                target,
+                reaction,
+            },
+            _,
+        ) => format!(
+            "react {} to {}",
+            reaction.content(),
+            comment_ref(*subject, *number, *target)
+        ),
+        (
+            Steer::Unreact {
+                number,
+                subject,
+                target,
+                reaction,
+            },
+            _,
+        ) => format!(
+            "remove {} reaction from {}",
+            reaction.content(),
+            comment_ref(*subject, *number, *target)
+        ),
+        (
+            Steer::Lock {
+                number, subject, ..
+            },
+            _,
+        ) => format!("lock {}", subject_ref(*subject, *number)),
+        (Steer::Unlock { number, subject }, _) => {
+            format!("unlock {}", subject_ref(*subject, *number))
+        }
+        (Steer::RerunChecks { number }, Some(SteerOutcome::ChecksRerun { run_ids, .. })) => {
+            format!("re-run {} failed runs on PR #{number}", run_ids.len())
+        }
+        (Steer::RerunChecks { number }, _) => format!("re-run failed checks on PR #{number}"),
+        (Steer::DispatchWorkflow { workflow, .. }, _) => {
+            format!("dispatch workflow {workflow}")
+        }
+        (Steer::CreateRelease { .. }, Some(SteerOutcome::ReleaseCreated { tag, url })) => {
+            format!("create release {tag} ({url})")
+        }
+        (Steer::CreateRelease { tag, .. }, _) => format!("create release {tag}"),
+        (Steer::WithdrawReviewRequest { number, .. }, _) => {
+            format!("withdraw review request on PR #{number}")
+        }
+    }
+}
+
+/// `issue #N` or `PR #N`.
+fn subject_ref(subject: Subject, number: u64) -> String {
+    match subject {
+        Subject::Issue => format!("issue #{number}"),
+        Subject::PullRequest => format!("PR #{number}"),
+    }
+}
+
+/// `comment N on issue #M`, or `review comment N on PR #M`.
+fn comment_ref(subject: Subject, number: u64, target: ReactionTarget) -> String {
+    match target {
+        ReactionTarget::Comment { comment_id } => {
+            format!("comment {comment_id} on {}", subject_ref(subject, number))
+        }
+        ReactionTarget::ReviewComment { comment_id } => {
+            format!(
+                "review comment {comment_id} on {}",
+                subject_ref(subject, number)
+            )
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! CI steer arguments: re-run checks, dispatch workflows.
+
+use std::collections::BTreeMap;
+
+use clap::Args;
+
+use crate::model::Steer;
+
+/// Arguments for `helm steer rerun-checks`.
+#[derive(Debug, Args)]
+pub struct RerunChecksArgs {
+    /// PR number.
+    number: u64,
+}
+
+impl RerunChecksArgs {
+    pub(super) fn build(&self) -> Steer {
+        Steer::RerunChecks {
+            number: self.number,
+        }
+    }
+}
+
+/// Arguments for `helm steer dispatch-workflow`.
+#[derive(Debug, Args)]
+pub struct DispatchWorkflowArgs {
+    /// Workflow file name (e.g. `deploy.yml`), ID, or name.
+    workflow: String,
+
+    /// Branch or tag to run on (default: the repository's default branch).
+    #[arg(long = "ref")]
+    git_ref: Option<String>,
+
+    /// Workflow input (repeatable): `NAME=VALUE`.
+    #[arg(long = "input", value_parser = parse_input)]
+    inputs: Vec<(String, String)>,
+}
+
+impl DispatchWorkflowArgs {
+    pub(super) fn build(&self) -> Result<Steer, String> {
+        let mut inputs = BTreeMap::new();
+        for (name, value) in &self.inputs {
+            if inputs.insert(name.clone(), value.clone()).is_some() {
+                return Err(format!("--input {name} given more than once"));
+            }
+        }
+        Ok(Steer::DispatchWorkflow {
+            workflow: self.workflow.clone(),
+            git_ref: self.git_ref.clone(),
+            inputs,
+        })
+    }
+}
+
+/// Parse an `--input` spec: `NAME=VALUE`. The value is everything after the first `=`.
+fn parse_input(spec: &str) -> Result<(String, String), String> {
+    match spec.split_once('=') {
+        Some((name, value)) if !name.is_empty() => Ok((name.to_string(), value.to_string())),
+        _ => Err("expected NAME=VALUE".into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_value_may_contain_equals() {
+        assert_eq!(
+            parse_input("filter=a=b").unwrap(),
+            ("filter".to_string(), "a=b".to_string())
+        );
+    }
+
+    #[test]
+    fn input_needs_a_name() {
+        assert!(parse_input("=value").is_err());
+        assert!(parse_input("value").is_err());
+    }
+}
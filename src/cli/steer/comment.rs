@@ -0,0 +1,58 @@
+//! `comment` steer arguments.
+
+use std::path::PathBuf;
+
+use clap::{ArgGroup, Args};
+
+use crate::model::{CommentTarget, Steer};
+
+use super::text::read_text;
+
+/// Arguments for `helm steer comment`.
+#[derive(Debug, Args)]
+#[command(group(ArgGroup::new("target").required(true).args(["issue", "pr"])))]
+pub struct CommentArgs {
+    /// Comment on this issue number.
+    #[arg(long, conflicts_with = "pr")]
+    issue: Option<u64>,
+
+    /// Comment on this PR number.
+    #[arg(long, conflicts_with = "issue")]
+    pr: Option<u64>,
+
+    /// Reply to this inline review comment ID (requires `--pr`).
+    #[arg(long, requires = "pr")]
+    reply_to: Option<u64>,
+
+    /// Comment body. `-` reads it from stdin.
+    #[arg(
+        long,
+        required_unless_present = "body_file",
+        conflicts_with = "body_file"
+    )]
+    body: Option<String>,
+
+    /// Read the comment body from this file (`-` for stdin).
+    #[arg(long)]
+    body_file: Option<PathBuf>,
+}
+
+impl CommentArgs {
+    pub(super) fn build(&self) -> Result<Steer, String> {
+        // Clap's ArgGroup ensures exactly one of --issue or --pr is present.
+        let (number, target) = match (self.issue, self.pr, self.reply_to) {
+            (Some(n), None, None) => (n, CommentTarget::Issue),
+            (None, Some(n), None) => (n, CommentTarget::PullRequest),
+            (None, Some(n), Some(id)) => (n, CommentTarget::ReviewFeedback { comment_id: id }),
+            _ => unreachable!("clap ArgGroup guarantees --issue or --pr is present"),
+        };
+        let Some(body) = read_text(self.body.as_deref(), self.body_file.as_ref())? else {
+            unreachable!("clap requires --body or --body-file");
+        };
+        Ok(Steer::Comment {
+            number,
+            body,
+            target,
+        })
+    }
+}
@@ -0,0 +1,135 @@
+//! Conversation steer arguments: react, lock.
+
+use clap::{ArgGroup, Args, ValueEnum};
+
+use crate::model::{LockReason, Reaction, ReactionTarget, Steer, Subject};
+
+use super::triage::SubjectArgs;
+
+/// Arguments for `helm steer react`.
+#[derive(Debug, Args)]
+#[command(group(
+    ArgGroup::new("on")
+        .required(true)
+        .args(["comment", "review_comment"])
+))]
+pub struct ReactArgs {
+    #[command(flatten)]
+    subject: SubjectArgs,
+
+    /// React to this comment in the issue's or PR's conversation.
+    #[arg(long)]
+    comment: Option<u64>,
+
+    /// React to this inline review comment (requires `--pr`).
+    #[arg(long)]
+    review_comment: Option<u64>,
+
+    /// The reaction.
+    #[arg(long, value_enum, allow_hyphen_values = true)]
+    reaction: ReactionArg,
+
+    /// Remove your reaction instead of adding it.
+    #[arg(long)]
+    remove: bool,
+}
+
+/// Reactions accepted by `react --reaction`, named as GitHub names them.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ReactionArg {
+    #[value(name = "+1", alias = "thumbs-up")]
+    ThumbsUp,
+    #[value(name = "-1", alias = "thumbs-down")]
+    ThumbsDown,
+    Laugh,
+    Confused,
+    Heart,
+    Hooray,
+    Rocket,
+    Eyes,
+}
+
+impl ReactArgs {
+    pub(super) fn build(&self) -> Result<Steer, String> {
+        let (number, subject) = self.subject.build();
+        // Clap's ArgGroup ensures exactly one of --comment or --review-comment is present.
+        let target = match (self.comment, self.review_comment) {
+            (Some(comment_id), None) => ReactionTarget::Comment { comment_id },
+            (None, Some(_)) if subject == Subject::Issue => {
+                return Err(
+                    "--review-comment requires --pr: issues have no review comments".into(),
+                );
+            }
+            (None, Some(comment_id)) => ReactionTarget::ReviewComment { comment_id },
+            _ => unreachable!("clap ArgGroup guarantees --comment or --review-comment is present"),
+        };
+        let reaction = match self.reaction {
+            ReactionArg::ThumbsUp => Reaction::ThumbsUp,
+            ReactionArg::ThumbsDown => Reaction::ThumbsDown,
+            ReactionArg::Laugh => Reaction::Laugh,
+            ReactionArg::Confused => Reaction::Confused,
+            ReactionArg::Heart => Reaction::Heart,
+            ReactionArg::Hooray => Reaction::Hooray,
+            ReactionArg::Rocket => Reaction::Rocket,
+            ReactionArg::Eyes => Reaction::Eyes,
+        };
+        Ok(if self.remove {
+            Steer::Unreact {
+                number,
+                subject,
+                target,
+                reaction,
+            }
+        } else {
+            Steer::React {
+                number,
+                subject,
+                target,
+                reaction,
+            }
+        })
+    }
+}
+
+/// Arguments for `helm steer lock`.
+#[derive(Debug, Args)]
+pub struct LockArgs {
+    #[command(flatten)]
+    subject: SubjectArgs,
+
+    /// Why the conversation is locked (default: no reason given).
+    #[arg(long, value_enum, conflicts_with = "unlock")]
+    reason: Option<LockReasonArg>,
+
+    /// Unlock the conversation instead of locking it.
+    #[arg(long)]
+    unlock: bool,
+}
+
+/// Lock reasons accepted by `lock --reason`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum LockReasonArg {
+    OffTopic,
+    Resolved,
+    Spam,
+    TooHeated,
+}
+
+impl LockArgs {
+    pub(super) fn build(&self) -> Steer {
+        let (number, subject) = self.subject.build();
+        if self.unlock {
+            return Steer::Unlock { number, subject };
+        }
+        Steer::Lock {
+            number,
+            subject,
+            reason: self.reason.map(|reason| match reason {
+                LockReasonArg::OffTopic => LockReason::OffTopic,
+                LockReasonArg::Resolved => LockReason::Resolved,
+                LockReasonArg::Spam => LockReason::Spam,
+                LockReasonArg::TooHeated => LockReason::TooHeated,
+            }),
+        }
+    }
+}
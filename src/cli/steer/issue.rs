@@ -0,0 +1,178 @@
+//! Issue steer arguments: create, edit, close, reopen.
+
+use std::path::PathBuf;
+
+use clap::{ArgGroup, Args, ValueEnum};
+
+use crate::model::{CloseReason, Steer};
+
+use super::text::{read_line, read_text};
+
+/// Arguments for `helm steer create-issue`.
+#[derive(Debug, Args)]
+pub struct CreateIssueArgs {
+    /// Issue title. `-` reads it from stdin.
+    #[arg(
+        long,
+        required_unless_present = "title_file",
+        conflicts_with = "title_file"
+    )]
+    title: Option<String>,
+
+    /// Read the issue title from this file (`-` for stdin).
+    #[arg(long)]
+    title_file: Option<PathBuf>,
+
+    /// Issue body. `-` reads it from stdin.
+    #[arg(
+        long,
+        required_unless_present = "body_file",
+        conflicts_with = "body_file"
+    )]
+    body: Option<String>,
+
+    /// Read the issue body from this file (`-` for stdin).
+    #[arg(long)]
+    body_file: Option<PathBuf>,
+
+    /// Label to apply (repeatable).
+    #[arg(long = "label")]
+    labels: Vec<String>,
+
+    /// User to assign (repeatable).
+    #[arg(long = "assignee")]
+    assignees: Vec<String>,
+
+    /// Milestone to attach, by name.
+    #[arg(long)]
+    milestone: Option<String>,
+}
+
+impl CreateIssueArgs {
+    pub(super) fn build(&self) -> Result<Steer, String> {
+        let Some(title) = read_line(self.title.as_deref(), self.title_file.as_ref())? else {
+            unreachable!("clap requires --title or --title-file");
+        };
+        let Some(body) = read_text(self.body.as_deref(), self.body_file.as_ref())? else {
+            unreachable!("clap requires --body or --body-file");
+        };
+        Ok(Steer::CreateIssue {
+            title,
+            body,
+            labels: self.labels.clone(),
+            assignees: self.assignees.clone(),
+            milestone: self.milestone.clone(),
+        })
+    }
+}
+
+/// Arguments for `helm steer edit-issue` and `helm steer edit-pr`.
+#[derive(Debug, Args)]
+#[command(group(
+    ArgGroup::new("changes")
+        .required(true)
+        .multiple(true)
+        .args(["title", "title_file", "body", "body_file", "add_labels", "remove_labels"])
+))]
+pub struct EditArgs {
+    /// Issue or PR number.
+    number: u64,
+
+    /// New title. `-` reads it from stdin.
+    #[arg(long, conflicts_with = "title_file")]
+    title: Option<String>,
+
+    /// Read the new title from this file (`-` for stdin).
+    #[arg(long)]
+    title_file: Option<PathBuf>,
+
+    /// New body. `-` reads it from stdin.
+    #[arg(long, conflicts_with = "body_file")]
+    body: Option<String>,
+
+    /// Read the new body from this file (`-` for stdin).
+    #[arg(long)]
+    body_file: Option<PathBuf>,
+
+    /// Label to add (repeatable).
+    #[arg(long = "add-label")]
+    add_labels: Vec<String>,
+
+    /// Label to remove (repeatable).
+    #[arg(long = "remove-label")]
+    remove_labels: Vec<String>,
+}
+
+impl EditArgs {
+    pub(super) fn build_issue(&self) -> Result<Steer, String> {
+        Ok(Steer::EditIssue {
+            number: self.number,
+            title: read_line(self.title.as_deref(), self.title_file.as_ref())?,
+            body: read_text(self.body.as_deref(), self.body_file.as_ref())?,
+            add_labels: self.add_labels.clone(),
+            remove_labels: self.remove_labels.clone(),
+        })
+    }
+
+    pub(super) fn build_pull_request(&self) -> Result<Steer, String> {
+        Ok(Steer::EditPullRequest {
+            number: self.number,
+            title: read_line(self.title.as_deref(), self.title_file.as_ref())?,
+            body: read_text(self.body.as_deref(), self.body_file.as_ref())?,
+            add_labels: self.add_labels.clone(),
+            remove_labels: self.remove_labels.clone(),
+        })
+    }
+}
+
+/// Arguments for `helm steer close-issue`.
+#[derive(Debug, Args)]
+pub struct CloseIssueArgs {
+    /// Issue number.
+    number: u64,
+
+    /// Why the issue is being closed (default: `completed`).
+    #[arg(long, value_enum, conflicts_with = "reopen")]
+    reason: Option<CloseReasonArg>,
+
+    /// Comment to post alongside the state change. `-` reads it from stdin.
+    #[arg(long, conflicts_with = "comment_file")]
+    comment: Option<String>,
+
+    /// Read the comment from this file (`-` for stdin).
+    #[arg(long)]
+    comment_file: Option<PathBuf>,
+
+    /// Reopen the issue instead of closing it.
+    #[arg(long)]
+    reopen: bool,
+}
+
+/// Close reasons accepted by `close-issue --reason`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum CloseReasonArg {
+    Completed,
+    NotPlanned,
+    Duplicate,
+}
+
+impl CloseIssueArgs {
+    pub(super) fn build(&self) -> Result<Steer, String> {
+        let comment = read_text(self.comment.as_deref(), self.comment_file.as_ref())?;
+        if self.reopen {
+            return Ok(Steer::ReopenIssue {
+                number: self.number,
+                comment,
+            });
+        }
+        Ok(Steer::CloseIssue {
+            number: self.number,
+            reason: match self.reason.unwrap_or(CloseReasonArg::Completed) {
+                CloseReasonArg::Completed => CloseReason::Completed,
+                CloseReasonArg::NotPlanned => CloseReason::NotPlanned,
+                CloseReasonArg::Duplicate => CloseReason::Duplicate,
+            },
+            comment,
+        })
+    }
+}
@@ -0,0 +1,336 @@
+//! Pull request steer arguments: create, close, reopen, draft state, update branch, merge.
+
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+
+use crate::model::{MergeStrategy, Observation, Observe, Payload, Steer};
+
+use super::text::{read_line, read_text};
+
+/// Arguments for `helm steer create-pr`.
+#[derive(Debug, Args)]
+pub struct CreatePullRequestArgs {
+    /// Branch containing the changes.
+    #[arg(long)]
+    head: String,
+
+    /// Branch the changes should merge into.
+    #[arg(long)]
+    base: String,
+
+    /// PR title. `-` reads it from stdin.
+    #[arg(
+        long,
+        required_unless_present = "title_file",
+        conflicts_with = "title_file"
+    )]
+    title: Option<String>,
+
+    /// Read the PR title from this file (`-` for stdin).
+    #[arg(long)]
+    title_file: Option<PathBuf>,
+
+    /// PR body. `-` reads it from stdin.
+    #[arg(
+        long,
+        required_unless_present = "body_file",
+        conflicts_with = "body_file"
+    )]
+    body: Option<String>,
+
+    /// Read the PR body from this file (`-` for stdin).
+    #[arg(long)]
+    body_file: Option<PathBuf>,
+
+    /// Open as a draft.
+    #[arg(long)]
+    draft: bool,
+
+    /// User to request review from (repeatable).
+    #[arg(long = "reviewer")]
+    reviewers: Vec<String>,
+}
+
+impl CreatePullRequestArgs {
+    pub(super) fn build(&self) -> Result<Steer, String> {
+        let Some(title) = read_line(self.title.as_deref(), self.title_file.as_ref())? else {
+            unreachable!("clap requires --title or --title-file");
+        };
+        let Some(body) = read_text(self.body.as_deref(), self.body_file.as_ref())? else {
+            unreachable!("clap requires --body or --body-file");
+        };
+        Ok(Steer::CreatePullRequest {
+            head: self.head.clone(),
+            base: self.base.clone(),
+            title,
+            body,
+            draft: self.draft,
+            reviewers: self.reviewers.clone(),
+        })
+    }
+}
+
+/// Arguments for `helm steer close-pr`.
+#[derive(Debug, Args)]
+pub struct ClosePullRequestArgs {
+    /// PR number.
+    number: u64,
+
+    /// Comment to post alongside the state change. `-` reads it from stdin.
+    #[arg(long, conflicts_with = "comment_file")]
+    comment: Option<String>,
+
+    /// Read the comment from this file (`-` for stdin).
+    #[arg(long)]
+    comment_file: Option<PathBuf>,
+
+    /// Reopen the PR instead of closing it.
+    #[arg(long)]
+    reopen: bool,
+}
+
+impl ClosePullRequestArgs {
+    pub(super) fn build(&self) -> Result<Steer, String> {
+        let comment = read_text(self.comment.as_deref(), self.comment_file.as_ref())?;
+        Ok(if self.reopen {
+            Steer::ReopenPullRequest {
+                number: self.number,
+                comment,
+            }
+        } else {
+            Steer::ClosePullRequest {
+                number: self.number,
+                comment,
+            }
+        })
+    }
+}
+
+/// Arguments for `helm steer ready-pr`.
+#[derive(Debug, Args)]
+pub struct ReadyPullRequestArgs {
+    /// PR number.
+    number: u64,
+
+    /// Convert the PR back to a draft instead.
+    #[arg(long)]
+    draft: bool,
+}
+
+impl ReadyPullRequestArgs {
+    pub(super) fn build(&self) -> Steer {
+        if self.draft {
+            Steer::ConvertToDraft {
+                number: self.number,
+            }
+        } else {
+            Steer::MarkReadyForReview {
+                number: self.number,
+            }
+        }
+    }
+}
+
+/// Arguments for `helm steer update-branch`.
+#[derive(Debug, Args)]
+pub struct UpdateBranchArgs {
+    /// PR number.
+    number: u64,
+
+    /// Rebase the head onto the base instead of merging the base in.
+    #[arg(long)]
+    rebase: bool,
+}
+
+impl UpdateBranchArgs {
+    pub(super) fn build(&self) -> Steer {
+        Steer::UpdateBranch {
+            number: self.number,
+            rebase: self.rebase,
+        }
+    }
+}
+
+/// Arguments for `helm steer merge-pr`.
+#[derive(Debug, Args)]
+pub struct MergePullRequestArgs {
+    /// PR number.
+    number: u64,
+
+    /// How the PR's commits land on the base branch.
+    #[arg(long, value_enum)]
+    strategy: MergeStrategyArg,
+
+    /// Delete the head branch after merging.
+    #[arg(long)]
+    delete_branch: bool,
+
+    /// Head commit SHA the merge decision was made against.
+    ///
+    /// May be abbreviated (7+ hex digits) when the PR is observed on the
+    /// slate; the observed full SHA is what the merge is pinned to.
+    #[arg(long, value_parser = parse_sha)]
+    expected_head: Option<String>,
+}
+
+/// Merge strategies accepted by `merge-pr --strategy`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum MergeStrategyArg {
+    Merge,
+    Squash,
+    Rebase,
+}
+
+impl MergePullRequestArgs {
+    pub(super) fn build(&self, slate: &[Observation]) -> Result<Steer, String> {
+        Ok(Steer::MergePullRequest {
+            number: self.number,
+            strategy: match self.strategy {
+                MergeStrategyArg::Merge => MergeStrategy::Merge,
+                MergeStrategyArg::Squash => MergeStrategy::Squash,
+                MergeStrategyArg::Rebase => MergeStrategy::Rebase,
+            },
+            delete_branch: self.delete_branch,
+            expected_head: resolve_expected_head(
+                self.number,
+                self.expected_head.as_deref(),
+                slate,
+            )?,
+        })
+    }
+}
+
+/// Length of a full commit SHA, which `gh pr merge --match-head-commit` needs.
+const FULL_SHA_LEN: usize = 40;
+
+/// Parse `--expected-head`: a commit SHA, full or abbreviated to 7+ hex digits.
+fn parse_sha(s: &str) -> Result<String, String> {
+    if (7..=FULL_SHA_LEN).contains(&s.len()) && s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        Ok(s.to_ascii_lowercase())
+    } else {
+        Err(format!(
+            "expected a commit SHA of 7 to {FULL_SHA_LEN} hex digits, got {s:?}"
+        ))
+    }
+}
+
+/// Decide which head commit a merge must match.
+///
+/// The slate's observation of the PR is what the bearing will seal, so it wins
+/// by default. An explicit `--expected-head` must agree with it when both exist —
+/// a merge never proceeds against code that differs from the sealed bearing.
+/// An abbreviated SHA is expanded from the observation, since GitHub only
+/// matches a full one.
+fn resolve_expected_head(
+    number: u64,
+    explicit: Option<&str>,
+    slate: &[Observation],
+) -> Result<String, String> {
+    let observed = slate.iter().find_map(|o| match (&o.target, &o.payload) {
+        (Observe::GitHubPullRequest { number: n }, Payload::GitHubPullRequest(pr))
+            if *n == number =>
+        {
+            pr.summary.as_ref().and_then(|s| s.head_sha.clone())
+        }
+        _ => None,
+    });
+
+    match (explicit, observed) {
+        (Some(e), Some(o)) if !o.starts_with(e) => Err(format!(
+            "--expected-head {e} does not match the head observed on the slate ({o}) \
+             for PR #{number}"
+        )),
+        (Some(e), None) if e.len() < FULL_SHA_LEN => Err(format!(
+            "--expected-head {e} is abbreviated and nothing on the slate expands it: \
+             pass the full SHA, or observe PR #{number} first \
+             (helm observe ... github-pr {number})"
+        )),
+        (Some(e), None) => Ok(e.to_string()),
+        (_, Some(o)) => Ok(o),
+        (None, None) => Err(format!(
+            "no head to guard the merge: observe PR #{number} first \
+             (helm observe ... github-pr {number}) or pass --expected-head"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use jiff::Timestamp;
+
+    use crate::model::{GitHubSummary, PullRequestPayload};
+
+    fn observed_pr(number: u64, head_sha: &str) -> Observation {
+        Observation {
+            target: Observe::GitHubPullRequest { number },
+            payload: Payload::GitHubPullRequest(Box::new(PullRequestPayload {
+                summary: Some(GitHubSummary {
+                    title: "Fix widget".into(),
+                    number,
+                    state: "OPEN".into(),
+                    author: "dyreby".into(),
+                    labels: vec![],
+                    assignees: vec![],
+                    head_branch: Some("fix-widget".into()),
+                    base_branch: Some("main".into()),
+                    head_sha: Some(head_sha.into()),
+                    body: None,
+                }),
+                files: vec![],
+                checks: vec![],
+                diff: None,
+                comments: vec![],
+                reviews: vec![],
+            })),
+            observed_at: Timestamp::now(),
+        }
+    }
+
+    const HEAD: &str = "abc1234def5678abc1234def5678abc1234def56";
+
+    #[test]
+    fn expected_head_from_slate() {
+        let slate = vec![observed_pr(45, HEAD)];
+        assert_eq!(resolve_expected_head(45, None, &slate).unwrap(), HEAD);
+    }
+
+    #[test]
+    fn expected_head_explicit_without_observation() {
+        assert_eq!(resolve_expected_head(45, Some(HEAD), &[]).unwrap(), HEAD);
+    }
+
+    #[test]
+    fn abbreviated_expected_head_expands_from_the_slate() {
+        let slate = vec![observed_pr(45, HEAD)];
+        assert_eq!(
+            resolve_expected_head(45, Some("abc1234"), &slate).unwrap(),
+            HEAD
+        );
+        let err = resolve_expected_head(45, Some("abc1234"), &[]).unwrap_err();
+        assert!(err.contains("abbreviated"));
+    }
+
+    #[test]
+    fn expected_head_must_agree_with_slate() {
+        let slate = vec![observed_pr(45, HEAD)];
+        let err = resolve_expected_head(45, Some("def4567"), &slate).unwrap_err();
+        assert!(err.contains("does not match"));
+    }
+
+    #[test]
+    fn expected_head_ignores_other_prs() {
+        let slate = vec![observed_pr(7, HEAD)];
+        assert!(resolve_expected_head(45, None, &slate).is_err());
+    }
+
+    #[test]
+    fn expected_head_must_look_like_a_sha() {
+        assert_eq!(parse_sha("ABC1234").unwrap(), "abc1234");
+        assert!(parse_sha("abc123").is_err());
+        assert!(parse_sha("main").is_err());
+        assert!(parse_sha(&format!("{HEAD}0")).is_err());
+    }
+}
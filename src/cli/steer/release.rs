@@ -0,0 +1,72 @@
+//! Release steer arguments: create.
+
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::model::Steer;
+
+use super::text::{read_line, read_text};
+
+/// Arguments for `helm steer create-release`.
+#[derive(Debug, Args)]
+pub struct CreateReleaseArgs {
+    /// Tag to release. Created from `--target` if it doesn't exist yet.
+    tag: String,
+
+    /// Branch or commit SHA to tag (default: the repository's default branch).
+    #[arg(long)]
+    target: Option<String>,
+
+    /// Release title. `-` reads it from stdin.
+    #[arg(
+        long,
+        required_unless_present = "title_file",
+        conflicts_with = "title_file"
+    )]
+    title: Option<String>,
+
+    /// Read the release title from this file (`-` for stdin).
+    #[arg(long)]
+    title_file: Option<PathBuf>,
+
+    /// Release notes. `-` reads them from stdin.
+    #[arg(
+        long,
+        required_unless_present = "notes_file",
+        conflicts_with = "notes_file"
+    )]
+    notes: Option<String>,
+
+    /// Read the release notes from this file (`-` for stdin).
+    #[arg(long)]
+    notes_file: Option<PathBuf>,
+
+    /// Save the release as a draft instead of publishing it.
+    #[arg(long)]
+    draft: bool,
+
+    /// Mark the release as a prerelease.
+    #[arg(long)]
+    prerelease: bool,
+}
+
+impl CreateReleaseArgs {
+    /// The notes are read now, so the logbook records exactly what was published.
+    pub(super) fn build(&self) -> Result<Steer, String> {
+        let Some(title) = read_line(self.title.as_deref(), self.title_file.as_ref())? else {
+            unreachable!("clap requires --title or --title-file");
+        };
+        let Some(notes) = read_text(self.notes.as_deref(), self.notes_file.as_ref())? else {
+            unreachable!("clap requires --notes or --notes-file");
+        };
+        Ok(Steer::CreateRelease {
+            tag: self.tag.clone(),
+            target: self.target.clone(),
+            title,
+            notes,
+            draft: self.draft,
+            prerelease: self.prerelease,
+        })
+    }
+}
@@ -0,0 +1,333 @@
+//! Review steer arguments: request and withdraw reviewers, submit reviews, resolve threads.
+
+use std::path::PathBuf;
+
+use clap::{ArgGroup, Args, ValueEnum};
+
+use crate::model::{DiffSide, InlineComment, ReviewVerdict, Steer};
+
+use super::text::read_text;
+
+/// Arguments for `helm steer request-review`.
+#[derive(Debug, Args)]
+#[command(group(
+    ArgGroup::new("who")
+        .required(true)
+        .multiple(true)
+        .args(["reviewers", "teams"])
+))]
+pub struct RequestReviewArgs {
+    /// PR number.
+    number: u64,
+
+    /// User to request review from (repeatable).
+    #[arg(long = "reviewer")]
+    reviewers: Vec<String>,
+
+    /// Team slug to request review from (repeatable).
+    #[arg(long = "team")]
+    teams: Vec<String>,
+
+    /// Withdraw the review requests instead of making them.
+    #[arg(long)]
+    remove: bool,
+}
+
+impl RequestReviewArgs {
+    pub(super) fn build(&self) -> Steer {
+        if self.remove {
+            Steer::WithdrawReviewRequest {
+                number: self.number,
+                reviewers: self.reviewers.clone(),
+                teams: self.teams.clone(),
+            }
+        } else {
+            Steer::RequestReview {
+                number: self.number,
+                reviewers: self.reviewers.clone(),
+                teams: self.teams.clone(),
+            }
+        }
+    }
+}
+
+/// Arguments for `helm steer resolve-thread`.
+#[derive(Debug, Args)]
+pub struct ResolveThreadArgs {
+    /// PR number.
+    number: u64,
+
+    /// Any review comment in the thread (the ID `comment --reply-to` takes).
+    #[arg(long)]
+    comment: u64,
+
+    /// Unresolve the thread instead.
+    #[arg(long)]
+    unresolve: bool,
+}
+
+impl ResolveThreadArgs {
+    pub(super) fn build(&self) -> Steer {
+        if self.unresolve {
+            Steer::UnresolveThread {
+                number: self.number,
+                comment_id: self.comment,
+            }
+        } else {
+            Steer::ResolveThread {
+                number: self.number,
+                comment_id: self.comment,
+            }
+        }
+    }
+}
+
+/// Arguments for `helm steer submit-review`.
+#[derive(Debug, Args)]
+pub struct SubmitReviewArgs {
+    /// PR number.
+    number: u64,
+
+    /// The review's verdict.
+    #[arg(long, value_enum)]
+    verdict: ReviewVerdictArg,
+
+    /// Summary body of the review (default: none). `-` reads it from stdin.
+    #[arg(long, conflicts_with = "body_file")]
+    body: Option<String>,
+
+    /// Read the summary body from this file (`-` for stdin).
+    #[arg(long)]
+    body_file: Option<PathBuf>,
+
+    /// New inline comment (repeatable): `PATH:LINE[-END][:left|right]=BODY`.
+    ///
+    /// `LINE-END` anchors to a range; the side defaults to `right` (the head version).
+    /// Example: `--inline 'src/widget.rs:10-12=Extract this into a helper.'`
+    #[arg(long = "inline", value_parser = parse_inline_comment)]
+    comments: Vec<InlineComment>,
+
+    /// New inline comment with its body read from a file (repeatable):
+    /// `PATH:LINE[-END][:left|right]=FILE`, with `-` for stdin.
+    #[arg(long = "inline-file", value_parser = parse_inline_comment)]
+    comment_files: Vec<InlineComment>,
+}
+
+/// Verdicts accepted by `submit-review --verdict`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ReviewVerdictArg {
+    Approve,
+    RequestChanges,
+    Comment,
+}
+
+impl SubmitReviewArgs {
+    /// GitHub refuses a review asking for changes without saying what, or a
+    /// comment review with nothing to say; both are caught here, before
+    /// anything is journaled.
+    pub(super) fn build(&self) -> Result<Steer, String> {
+        let body = read_text(self.body.as_deref(), self.body_file.as_ref())?.unwrap_or_default();
+        let comments: Vec<_> = self
+            .comments
+            .iter()
+            .cloned()
+            .map(Ok)
+            .chain(self.comment_files.iter().map(read_inline_file))
+            .collect::<Result<_, String>>()?;
+        let verdict = match self.verdict {
+            ReviewVerdictArg::Approve => ReviewVerdict::Approve,
+            ReviewVerdictArg::RequestChanges => ReviewVerdict::RequestChanges,
+            ReviewVerdictArg::Comment => ReviewVerdict::Comment,
+        };
+        let blank = body.trim().is_empty();
+        match verdict {
+            ReviewVerdict::RequestChanges if blank => {
+                return Err(
+                    "--verdict request-changes needs a --body saying what to change".into(),
+                );
+            }
+            ReviewVerdict::Comment if blank && comments.is_empty() => {
+                return Err("--verdict comment needs a --body or at least one --inline".into());
+            }
+            _ => {}
+        }
+        Ok(Steer::SubmitReview {
+            number: self.number,
+            verdict,
+            body,
+            comments,
+        })
+    }
+}
+
+/// Read the body of an `--inline-file` comment from the file it names.
+fn read_inline_file(comment: &InlineComment) -> Result<InlineComment, String> {
+    let path = PathBuf::from(&comment.body);
+    let Some(body) = read_text(None, Some(&path))? else {
+        unreachable!("a file was given");
+    };
+    Ok(InlineComment {
+        body,
+        ..comment.clone()
+    })
+}
+
+/// Parse an `--inline` or `--inline-file` spec: `PATH:LINE[-END][:left|right]=BODY`.
+///
+/// Paths and bodies may both contain `=`, so the body starts after the first
+/// `=` whose left side reads as a location. The location is read from the
+/// right so paths containing `:` still parse.
+fn parse_inline_comment(spec: &str) -> Result<InlineComment, String> {
+    let mut first_error = None;
+    for (i, _) in spec.match_indices('=') {
+        match parse_location(&spec[..i]) {
+            Ok((path, start_line, line, side)) => {
+                return Ok(InlineComment {
+                    path: path.to_string(),
+                    line,
+                    start_line,
+                    side,
+                    body: spec[i + 1..].to_string(),
+                });
+            }
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    Err(first_error.unwrap_or_else(|| "expected PATH:LINE[-END][:left|right]=BODY".to_string()))
+}
+
+/// Parse `PATH:LINE[-END][:left|right]` into its path, start line, line, and side.
+fn parse_location(location: &str) -> Result<(&str, Option<u64>, u64, DiffSide), String> {
+    let (rest, side) = match location.rsplit_once(':') {
+        Some((rest, "left")) => (rest, DiffSide::Left),
+        Some((rest, "right")) => (rest, DiffSide::Right),
+        _ => (location, DiffSide::Right),
+    };
+
+    let (path, lines) = rest
+        .rsplit_once(':')
+        .ok_or_else(|| format!("missing line number in '{location}'"))?;
+    if path.is_empty() {
+        return Err(format!("missing path in '{location}'"));
+    }
+
+    let parse_line = |s: &str| {
+        s.parse::<u64>()
+            .map_err(|_| format!("invalid line number '{s}' in '{location}'"))
+    };
+    let (start_line, line) = match lines.split_once('-') {
+        Some((start, end)) => {
+            let (start, end) = (parse_line(start)?, parse_line(end)?);
+            if start >= end {
+                return Err(format!("range {start}-{end} must run forward"));
+            }
+            (Some(start), end)
+        }
+        None => (None, parse_line(lines)?),
+    };
+    Ok((path, start_line, line, side))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inline_single_line_defaults_to_right() {
+        let c = parse_inline_comment("src/widget.rs:42=Nit: rename.").unwrap();
+        assert_eq!(c.path, "src/widget.rs");
+        assert_eq!(c.line, 42);
+        assert_eq!(c.start_line, None);
+        assert_eq!(c.side, DiffSide::Right);
+        assert_eq!(c.body, "Nit: rename.");
+    }
+
+    #[test]
+    fn inline_range_on_left_side() {
+        let c = parse_inline_comment("src/lib.rs:10-12:left=Why was this removed?").unwrap();
+        assert_eq!(c.start_line, Some(10));
+        assert_eq!(c.line, 12);
+        assert_eq!(c.side, DiffSide::Left);
+    }
+
+    #[test]
+    fn inline_body_may_contain_equals() {
+        let c = parse_inline_comment("a.rs:1=use x = y").unwrap();
+        assert_eq!(c.body, "use x = y");
+    }
+
+    #[test]
+    fn inline_path_may_contain_equals() {
+        let c = parse_inline_comment("docs/a=b.md:3:left=set x=1 here").unwrap();
+        assert_eq!(c.path, "docs/a=b.md");
+        assert_eq!(c.line, 3);
+        assert_eq!(c.side, DiffSide::Left);
+        assert_eq!(c.body, "set x=1 here");
+    }
+
+    #[test]
+    fn inline_file_bodies_are_read_from_the_file() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("nit.md");
+        std::fs::write(&path, "Extract this.\n\nIt's used twice.\n").unwrap();
+
+        let spec = parse_inline_comment(&format!("src/lib.rs:10={}", path.display())).unwrap();
+        let c = read_inline_file(&spec).unwrap();
+        assert_eq!(c.path, "src/lib.rs");
+        assert_eq!(c.line, 10);
+        assert_eq!(c.body, "Extract this.\n\nIt's used twice.\n");
+    }
+
+    fn review(verdict: ReviewVerdictArg, body: Option<&str>, inline: &[&str]) -> SubmitReviewArgs {
+        SubmitReviewArgs {
+            number: 45,
+            verdict,
+            body: body.map(str::to_string),
+            body_file: None,
+            comments: inline
+                .iter()
+                .map(|spec| parse_inline_comment(spec).unwrap())
+                .collect(),
+            comment_files: vec![],
+        }
+    }
+
+    #[test]
+    fn reviews_that_github_would_refuse_are_rejected() {
+        assert!(
+            review(
+                ReviewVerdictArg::RequestChanges,
+                None,
+                &["a.rs:1=Fix this."]
+            )
+            .build()
+            .unwrap_err()
+            .contains("request-changes needs a --body")
+        );
+        assert!(
+            review(ReviewVerdictArg::Comment, Some("  "), &[])
+                .build()
+                .is_err()
+        );
+
+        review(ReviewVerdictArg::Approve, None, &[])
+            .build()
+            .unwrap();
+        review(ReviewVerdictArg::Comment, None, &["a.rs:1=Nit."])
+            .build()
+            .unwrap();
+        review(ReviewVerdictArg::RequestChanges, Some("See inline."), &[])
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn inline_rejects_malformed_specs() {
+        assert!(parse_inline_comment("src/lib.rs:10").is_err());
+        assert!(parse_inline_comment("src/lib.rs=body").is_err());
+        assert!(parse_inline_comment(":3=body").is_err());
+        assert!(parse_inline_comment("a.rs:12-10=body").is_err());
+    }
+}
@@ -0,0 +1,242 @@
+//! Template mode: interpolate the voyage into a steer's text.
+//!
+//! With `helm steer --template`, every title, body, comment, and note the
+//! steer carries is rendered before the steer is sealed, so the logbook,
+//! the proposal, and the dry run all hold the rendered text. Placeholders:
+//!
+//! - `{{intent}}` — the voyage's intent.
+//! - `{{slate}}` — a Markdown list of what is on the slate, i.e. the bearing.
+//! - `{{logbook}}` — a Markdown list of the voyage's logbook entries so far.
+//!
+//! Anything else between `{{` and `}}` is an error rather than left in place,
+//! so a typo never reaches GitHub.
+
+use crate::{
+    cli::observe::describe_observe,
+    model::{EntryKind, LogbookEntry, Observation, Steer, Voyage},
+    storage::Storage,
+};
+
+/// What the placeholders expand to.
+pub(super) struct Context {
+    intent: String,
+    slate: String,
+    logbook: String,
+}
+
+impl Context {
+    /// Gather the voyage's intent, the slate about to be sealed, and its logbook.
+    pub(super) fn load(
+        storage: &Storage,
+        voyage: &Voyage,
+        slate: &[Observation],
+    ) -> Result<Self, String> {
+        let logbook = storage
+            .load_logbook(voyage.id)
+            .map_err(|e| format!("failed to load logbook: {e}"))?;
+        Ok(Self::new(&voyage.intent, slate, &logbook))
+    }
+
+    fn new(intent: &str, slate: &[Observation], logbook: &[LogbookEntry]) -> Self {
+        Self {
+            intent: intent.to_string(),
+            slate: markdown_list(slate.iter().map(|o| describe_observe(&o.target))),
+            logbook: markdown_list(logbook.iter().map(describe_entry)),
+        }
+    }
+}
+
+/// Render every text field of `steer` in place.
+pub(super) fn render(steer: &mut Steer, context: &Context) -> Result<(), String> {
+    for text in text_fields(steer) {
+        *text = expand(text, context)?;
+    }
+    Ok(())
+}
+
+/// The free-form text a steer carries — what a template may appear in.
+fn text_fields(steer: &mut Steer) -> Vec<&mut String> {
+    match steer {
+        Steer::Comment { body, .. } => vec![body],
+        Steer::CreateIssue { title, body, .. } | Steer::CreatePullRequest { title, body, .. } => {
+            vec![title, body]
+        }
+        Steer::EditIssue { title, body, .. } | Steer::EditPullRequest { title, body, .. } => {
+            title.iter_mut().chain(body.iter_mut()).collect()
+        }
+        Steer::CloseIssue { comment, .. }
+        | Steer::ReopenIssue { comment, .. }
+        | Steer::ClosePullRequest { comment, .. }
+        | Steer::ReopenPullRequest { comment, .. } => comment.iter_mut().collect(),
+        Steer::SubmitReview { body, comments, .. } => std::iter::once(body)
+            .chain(comments.iter_mut().map(|c| &mut c.body))
+            .collect(),
+        Steer::CreateRelease { title, notes, .. } => vec![title, notes],
+        Steer::Label { .. }
+        | Steer::Assign { .. }
+        | Steer::SetMilestone { .. }
+        | Steer::MarkReadyForReview { .. }
+        | Steer::ConvertToDraft { .. }
+        | Steer::UpdateBranch { .. }
+        | Steer::RequestReview { .. }
+        | Steer::WithdrawReviewRequest { .. }
+        | Steer::ResolveThread { .. }
+        | Steer::UnresolveThread { .. }
+        | Steer::React { .. }
+        | Steer::Unreact { .. }
+        | Steer::Lock { .. }
+        | Steer::Unlock { .. }
+        | Steer::RerunChecks { .. }
+        | Steer::DispatchWorkflow { .. }
+        | Steer::MergePullRequest { .. } => vec![],
+    }
+}
+
+/// Replace each `{{name}}` in `text` with its value.
+fn expand(text: &str, context: &Context) -> Result<String, String> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find("}}")
+            .ok_or_else(|| format!("unclosed template placeholder: {}", &rest[start..]))?;
+        let value = match after[..end].trim() {
+            "intent" => &context.intent,
+            "slate" => &context.slate,
+            "logbook" => &context.logbook,
+            name => {
+                return Err(format!(
+                    "unknown template placeholder {{{{{name}}}}} \
+                     (expected {{{{intent}}}}, {{{{slate}}}}, or {{{{logbook}}}})"
+                ));
+            }
+        };
+        out.push_str(value);
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// One line of `{{logbook}}`: who did what, and why.
+fn describe_entry(entry: &LogbookEntry) -> String {
+    let what = match &entry.kind {
+        EntryKind::Steer { steer, outcome } => super::describe_steer(steer, outcome.as_ref()),
+        EntryKind::Log(status) => format!("logged \"{status}\""),
+    };
+    format!(
+        "{} as {}: {what} — {}",
+        entry.identity, entry.role, entry.bearing.summary
+    )
+}
+
+/// A Markdown bullet list, or `(none)` when there is nothing to list.
+fn markdown_list(items: impl Iterator<Item = String>) -> String {
+    let lines: Vec<_> = items.map(|item| format!("- {item}")).collect();
+    if lines.is_empty() {
+        "(none)".to_string()
+    } else {
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use jiff::Timestamp;
+
+    use crate::model::{Bearing, CommentTarget, IssuePayload, Observe, Payload, SteerOutcome};
+
+    fn observed_issue(number: u64) -> Observation {
+        Observation {
+            target: Observe::GitHubIssue { number },
+            payload: Payload::GitHubIssue(Box::new(IssuePayload {
+                summary: None,
+                comments: vec![],
+            })),
+            observed_at: Timestamp::now(),
+        }
+    }
+
+    fn entry(kind: EntryKind, summary: &str) -> LogbookEntry {
+        LogbookEntry {
+            bearing: Bearing {
+                observations: vec![],
+                summary: summary.into(),
+            },
+            identity: "dyreby".into(),
+            role: "coder".into(),
+            method: "conversation".into(),
+            recorded_at: Timestamp::now(),
+            kind,
+            approval: None,
+        }
+    }
+
+    fn context() -> Context {
+        let logbook = vec![
+            entry(
+                EntryKind::Steer {
+                    steer: Steer::Comment {
+                        number: 42,
+                        body: "Plan".into(),
+                        target: CommentTarget::Issue,
+                    },
+                    outcome: Some(SteerOutcome::CommentPosted {
+                        id: 1,
+                        url: "https://github.com/dyreby/helm/issues/42#issuecomment-1".into(),
+                    }),
+                },
+                "Posted the plan",
+            ),
+            entry(EntryKind::Log("waiting".into()), "Waiting on review"),
+        ];
+        Context::new("Fix the widget", &[observed_issue(42)], &logbook)
+    }
+
+    #[test]
+    fn placeholders_expand_into_the_voyage() {
+        let text = "Intent: {{intent}}\n\nSeen:\n{{ slate }}\n\nSo far:\n{{logbook}}";
+        assert_eq!(
+            expand(text, &context()).unwrap(),
+            "Intent: Fix the widget\n\nSeen:\n- issue #42\n\nSo far:\n\
+             - dyreby as coder: comment on issue #42 — Posted the plan\n\
+             - dyreby as coder: logged \"waiting\" — Waiting on review"
+        );
+    }
+
+    #[test]
+    fn empty_lists_render_as_none() {
+        let context = Context::new("Fix the widget", &[], &[]);
+        assert_eq!(expand("{{slate}}", &context).unwrap(), "(none)");
+    }
+
+    #[test]
+    fn unknown_placeholder_is_an_error() {
+        let err = expand("Hi {{author}}", &context()).unwrap_err();
+        assert!(err.contains("unknown template placeholder {{author}}"));
+        assert!(expand("Hi {{intent", &context()).is_err());
+    }
+
+    #[test]
+    fn render_covers_every_text_field() {
+        let mut steer = Steer::CreateRelease {
+            tag: "v1.0.0".into(),
+            target: None,
+            title: "{{intent}}".into(),
+            notes: "Bearing:\n{{slate}}".into(),
+            draft: false,
+            prerelease: false,
+        };
+        render(&mut steer, &context()).unwrap();
+
+        let Steer::CreateRelease { title, notes, .. } = steer else {
+            unreachable!()
+        };
+        assert_eq!(title, "Fix the widget");
+        assert_eq!(notes, "Bearing:\n- issue #42");
+    }
+}
@@ -0,0 +1,111 @@
+//! Long-form text arguments: given inline, read from a file, or piped on stdin.
+//!
+//! Each text field (`--title`, `--body`, `--comment`, `--notes`) has a
+//! `-file` sibling, and `-` in either place reads stdin. Bodies longer than
+//! a line survive intact instead of going through shell quoting.
+//!
+//! Stdin can feed only one field per command: a second `-` is refused
+//! rather than read as empty.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// Whether a field has already taken stdin. Stdin is the process's, and
+/// only one read of it gets anything.
+static STDIN_TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// Resolve a text field from its inline value or its file, whichever was given.
+///
+/// Clap keeps the two mutually exclusive. Returns `None` when neither was given.
+pub(super) fn read_text(
+    inline: Option<&str>,
+    file: Option<&PathBuf>,
+) -> Result<Option<String>, String> {
+    match (inline, file) {
+        (Some("-"), _) => read_stdin().map(Some),
+        (Some(text), _) => Ok(Some(text.to_string())),
+        (None, Some(path)) if path == Path::new("-") => read_stdin().map(Some),
+        (None, Some(path)) => fs::read_to_string(path)
+            .map(Some)
+            .map_err(|e| format!("failed to read {}: {e}", path.display())),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Resolve a one-line field, like a title, from its inline value or its file.
+///
+/// Text from a file or stdin loses its trailing newline.
+pub(super) fn read_line(
+    inline: Option<&str>,
+    file: Option<&PathBuf>,
+) -> Result<Option<String>, String> {
+    Ok(read_text(inline, file)?.map(|text| text.trim_end_matches(['\n', '\r']).to_string()))
+}
+
+fn read_stdin() -> Result<String, String> {
+    take_stdin()?;
+    io::read_to_string(io::stdin()).map_err(|e| format!("failed to read stdin: {e}"))
+}
+
+/// Claim stdin for one field, refusing a second.
+fn take_stdin() -> Result<(), String> {
+    if STDIN_TAKEN.swap(true, Ordering::SeqCst) {
+        return Err("only one field can be read from stdin (`-`)".into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::TempDir;
+
+    #[test]
+    fn inline_text_is_taken_as_is() {
+        assert_eq!(
+            read_text(Some("Here's my plan."), None).unwrap().as_deref(),
+            Some("Here's my plan.")
+        );
+    }
+
+    #[test]
+    fn file_text_keeps_its_lines() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("plan.md");
+        fs::write(&path, "## Plan\n\n1. Fix the widget.\n2. Ship it.\n").unwrap();
+
+        let text = read_text(None, Some(&path)).unwrap().unwrap();
+        assert_eq!(text, "## Plan\n\n1. Fix the widget.\n2. Ship it.\n");
+    }
+
+    #[test]
+    fn missing_file_is_an_error() {
+        let err = read_text(None, Some(&PathBuf::from("/nonexistent/plan.md"))).unwrap_err();
+        assert!(err.contains("failed to read /nonexistent/plan.md"));
+    }
+
+    #[test]
+    fn lines_from_files_lose_their_newline() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("title.txt");
+        fs::write(&path, "Fix the widget\n").unwrap();
+
+        let title = read_line(None, Some(&path)).unwrap().unwrap();
+        assert_eq!(title, "Fix the widget");
+    }
+
+    #[test]
+    fn stdin_feeds_only_one_field() {
+        take_stdin().unwrap();
+        assert!(take_stdin().unwrap_err().contains("only one field"));
+    }
+
+    #[test]
+    fn neither_given_is_none() {
+        assert!(read_text(None, None).unwrap().is_none());
+    }
+}
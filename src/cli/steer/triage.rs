@@ -0,0 +1,122 @@
+//! Triage steer arguments: label, assign, milestone.
+
+use clap::{ArgGroup, Args};
+
+use crate::model::{Steer, Subject};
+
+/// The issue or PR a triage steer acts on.
+#[derive(Debug, Args)]
+#[command(group(ArgGroup::new("subject").required(true).args(["issue", "pr"])))]
+pub struct SubjectArgs {
+    /// Issue number.
+    #[arg(long, conflicts_with = "pr")]
+    issue: Option<u64>,
+
+    /// PR number.
+    #[arg(long, conflicts_with = "issue")]
+    pr: Option<u64>,
+}
+
+impl SubjectArgs {
+    pub(super) fn build(&self) -> (u64, Subject) {
+        // Clap's ArgGroup ensures exactly one of --issue or --pr is present.
+        match (self.issue, self.pr) {
+            (Some(n), None) => (n, Subject::Issue),
+            (None, Some(n)) => (n, Subject::PullRequest),
+            _ => unreachable!("clap ArgGroup guarantees --issue or --pr is present"),
+        }
+    }
+}
+
+/// Arguments for `helm steer label`.
+#[derive(Debug, Args)]
+#[command(group(
+    ArgGroup::new("changes")
+        .required(true)
+        .multiple(true)
+        .args(["add", "remove"])
+))]
+pub struct LabelArgs {
+    #[command(flatten)]
+    subject: SubjectArgs,
+
+    /// Label to add (repeatable).
+    #[arg(long)]
+    add: Vec<String>,
+
+    /// Label to remove (repeatable).
+    #[arg(long)]
+    remove: Vec<String>,
+}
+
+impl LabelArgs {
+    pub(super) fn build(&self) -> Steer {
+        let (number, subject) = self.subject.build();
+        Steer::Label {
+            number,
+            subject,
+            add: self.add.clone(),
+            remove: self.remove.clone(),
+        }
+    }
+}
+
+/// Arguments for `helm steer assign`.
+#[derive(Debug, Args)]
+#[command(group(
+    ArgGroup::new("changes")
+        .required(true)
+        .multiple(true)
+        .args(["add", "remove"])
+))]
+pub struct AssignArgs {
+    #[command(flatten)]
+    subject: SubjectArgs,
+
+    /// User to assign (repeatable). `@me` assigns the acting identity.
+    #[arg(long)]
+    add: Vec<String>,
+
+    /// User to unassign (repeatable).
+    #[arg(long)]
+    remove: Vec<String>,
+}
+
+impl AssignArgs {
+    pub(super) fn build(&self) -> Steer {
+        let (number, subject) = self.subject.build();
+        Steer::Assign {
+            number,
+            subject,
+            add: self.add.clone(),
+            remove: self.remove.clone(),
+        }
+    }
+}
+
+/// Arguments for `helm steer milestone`.
+#[derive(Debug, Args)]
+#[command(group(ArgGroup::new("change").required(true).args(["set", "clear"])))]
+pub struct MilestoneArgs {
+    #[command(flatten)]
+    subject: SubjectArgs,
+
+    /// Milestone to set, by name.
+    #[arg(long)]
+    set: Option<String>,
+
+    /// Remove the current milestone.
+    #[arg(long)]
+    clear: bool,
+}
+
+impl MilestoneArgs {
+    pub(super) fn build(&self) -> Steer {
+        let (number, subject) = self.subject.build();
+        Steer::SetMilestone {
+            number,
+            subject,
+            milestone: self.set.clone(),
+        }
+    }
+}
@@ -0,0 +1,433 @@
+//! Hooks: external executables run around steers and logs.
+//!
+//! Configured under `[hooks]` in `~/.helm/config.toml`:
+//!
+//! ```toml
+//! [hooks]
+//! pre-steer = ["/usr/local/bin/lint-steer"]
+//! post-steer = ["/home/me/bin/notify"]
+//! pre-log = []
+//! post-log = ["audit-helm"]
+//! timeout-secs = 30
+//! ```
+//!
+//! Each entry is an absolute path or a command on `PATH`, run without a shell.
+//! A hook gets a [`HookInput`] as JSON on stdin; its stdout goes to helm's
+//! stderr so it can't mix with helm's own output.
+//!
+//! Pre-hooks run in order before the action; the first to exit non-zero
+//! aborts it. Post-hooks run after the entry is recorded; a failing one
+//! is reported, but what's done is done.
+//!
+//! A hook still running after `timeout-secs` (default 30) is killed and
+//! counts as failed.
+
+use std::{
+    fs, io,
+    io::Write as _,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::model::{Approval, Observation, Provenance, Steer, SteerOutcome};
+
+/// How long a hook may run when `timeout-secs` isn't set.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often a running hook is checked for having exited.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(Deserialize)]
+struct Config {
+    #[serde(default)]
+    hooks: Hooks,
+}
+
+/// The hooks configured for each event.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Hooks {
+    #[serde(default)]
+    pre_steer: Vec<PathBuf>,
+
+    #[serde(default)]
+    post_steer: Vec<PathBuf>,
+
+    #[serde(default)]
+    pre_log: Vec<PathBuf>,
+
+    #[serde(default)]
+    post_log: Vec<PathBuf>,
+
+    /// Seconds each hook may run before it's killed.
+    #[serde(default)]
+    timeout_secs: Option<u64>,
+}
+
+/// When a hook runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Event {
+    /// Before a steer is journaled and performed.
+    PreSteer,
+
+    /// After a steer is performed and recorded.
+    PostSteer,
+
+    /// Before a log entry is recorded.
+    PreLog,
+
+    /// After a log entry is recorded.
+    PostLog,
+}
+
+/// What a hook reads on stdin.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookInput<'a> {
+    pub event: Event,
+    pub voyage_id: Uuid,
+
+    /// Who is acting. For an approved proposal, the proposer.
+    pub provenance: &'a Provenance,
+    pub summary: &'a str,
+    pub action: HookAction<'a>,
+
+    /// The observations sealed, or about to be sealed, into the entry's bearing.
+    pub slate: &'a [Observation],
+}
+
+/// The steer or log a hook runs around.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum HookAction<'a> {
+    Steer {
+        steer: &'a Steer,
+
+        /// What the steer produced. Always `null` for pre-steer hooks.
+        outcome: Option<&'a SteerOutcome>,
+
+        /// The co-signature, when the steer is an approved proposal.
+        approval: Option<&'a Approval>,
+    },
+
+    Log {
+        status: &'a str,
+    },
+}
+
+/// Load the `[hooks]` table from `~/.helm/config.toml`; none if it's missing.
+pub fn load_hooks() -> Result<Hooks, String> {
+    let Some(home) = dirs::home_dir() else {
+        return Ok(Hooks::default());
+    };
+    load_from(&home.join(".helm").join("config.toml"))
+}
+
+/// Inner loading logic — separated from environment I/O for testability.
+fn load_from(path: &Path) -> Result<Hooks, String> {
+    let contents = match fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Hooks::default()),
+        Err(e) => return Err(format!("failed to read {}: {e}", path.display())),
+    };
+
+    let config: Config = toml::from_str(&contents)
+        .map_err(|e| format!("failed to parse {}: {e}", path.display()))?;
+    Ok(config.hooks)
+}
+
+impl Hooks {
+    /// Run the hooks for `input.event`.
+    ///
+    /// Pre-hooks stop at the first failure and return it, which aborts the action.
+    /// Post-hooks all run; their failures are returned together.
+    pub fn run(&self, input: &HookInput<'_>) -> Result<(), String> {
+        let (hooks, pre) = match input.event {
+            Event::PreSteer => (&self.pre_steer, true),
+            Event::PostSteer => (&self.post_steer, false),
+            Event::PreLog => (&self.pre_log, true),
+            Event::PostLog => (&self.post_log, false),
+        };
+        if hooks.is_empty() {
+            return Ok(());
+        }
+
+        let json = serde_json::to_vec(input)
+            .map_err(|e| format!("failed to serialize hook input: {e}"))?;
+        let timeout = self
+            .timeout_secs
+            .map_or(DEFAULT_TIMEOUT, Duration::from_secs);
+        let mut failures = Vec::new();
+        for hook in hooks {
+            if let Err(e) = run_hook(hook, &json, timeout) {
+                if pre {
+                    return Err(e);
+                }
+                failures.push(e);
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures.join("\n"))
+        }
+    }
+}
+
+/// Run one hook with `input` on stdin, failing unless it exits zero within `timeout`.
+fn run_hook(hook: &Path, input: &[u8], timeout: Duration) -> Result<(), String> {
+    let mut child = Command::new(hook)
+        .stdin(Stdio::piped())
+        .stdout(io::stderr())
+        .spawn()
+        .map_err(|e| format!("failed to run hook {}: {e}", hook.display()))?;
+
+    // Write from another thread: a hook that never reads would otherwise
+    // block the write past its timeout.
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| format!("failed to open stdin of hook {}", hook.display()))?;
+    let input = input.to_vec();
+    let writer = thread::spawn(move || stdin.write_all(&input));
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => thread::sleep(POLL_INTERVAL),
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!(
+                    "hook {} timed out after {}s",
+                    hook.display(),
+                    timeout.as_secs()
+                ));
+            }
+            Err(e) => return Err(format!("failed to run hook {}: {e}", hook.display())),
+        }
+    };
+
+    // A hook that doesn't read its input is free to exit without it.
+    if writer.is_finished()
+        && let Ok(Err(e)) = writer.join()
+        && e.kind() != io::ErrorKind::BrokenPipe
+    {
+        return Err(format!("failed to write to hook {}: {e}", hook.display()));
+    }
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("hook {} failed ({status})", hook.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::os::unix::fs::PermissionsExt as _;
+
+    use tempfile::TempDir;
+
+    use crate::model::CommentTarget;
+
+    /// Write an executable shell script into `dir`.
+    fn script(dir: &TempDir, name: &str, body: &str) -> PathBuf {
+        let path = dir.path().join(name);
+        fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    fn provenance() -> Provenance {
+        Provenance {
+            identity: "agent-x".into(),
+            role: "coder".into(),
+            method: "automated".into(),
+        }
+    }
+
+    fn steer() -> Steer {
+        Steer::Comment {
+            number: 42,
+            body: "Here's my plan.".into(),
+            target: CommentTarget::Issue,
+        }
+    }
+
+    fn input<'a>(event: Event, provenance: &'a Provenance, steer: &'a Steer) -> HookInput<'a> {
+        HookInput {
+            event,
+            voyage_id: Uuid::nil(),
+            provenance,
+            summary: "Plan is ready",
+            action: HookAction::Steer {
+                steer,
+                outcome: None,
+                approval: None,
+            },
+            slate: &[],
+        }
+    }
+
+    #[test]
+    fn hook_reads_input_on_stdin() {
+        let tmp = TempDir::new().unwrap();
+        let out = tmp.path().join("input.json");
+        let hook = script(&tmp, "capture", &format!("cat > {}", out.display()));
+        let hooks = Hooks {
+            pre_steer: vec![hook],
+            ..Hooks::default()
+        };
+
+        let (provenance, steer) = (provenance(), steer());
+        hooks
+            .run(&input(Event::PreSteer, &provenance, &steer))
+            .unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(out).unwrap()).unwrap();
+        assert_eq!(json["event"], "pre-steer");
+        assert_eq!(json["voyageId"], Uuid::nil().to_string());
+        assert_eq!(json["provenance"]["identity"], "agent-x");
+        assert_eq!(json["action"]["kind"], "steer");
+        assert_eq!(json["action"]["steer"]["kind"], "comment");
+        assert_eq!(json["slate"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn failing_pre_hook_stops_the_rest() {
+        let tmp = TempDir::new().unwrap();
+        let marker = tmp.path().join("ran");
+        let hooks = Hooks {
+            pre_steer: vec![
+                script(&tmp, "deny", "exit 3"),
+                script(&tmp, "after", &format!("touch {}", marker.display())),
+            ],
+            ..Hooks::default()
+        };
+
+        let (provenance, steer) = (provenance(), steer());
+        let err = hooks
+            .run(&input(Event::PreSteer, &provenance, &steer))
+            .unwrap_err();
+
+        assert!(err.contains("deny"));
+        assert!(!marker.exists());
+    }
+
+    #[test]
+    fn failing_post_hook_still_runs_the_rest() {
+        let tmp = TempDir::new().unwrap();
+        let marker = tmp.path().join("ran");
+        let hooks = Hooks {
+            post_steer: vec![
+                script(&tmp, "broken", "exit 1"),
+                script(&tmp, "after", &format!("touch {}", marker.display())),
+            ],
+            ..Hooks::default()
+        };
+
+        let (provenance, steer) = (provenance(), steer());
+        let err = hooks
+            .run(&input(Event::PostSteer, &provenance, &steer))
+            .unwrap_err();
+
+        assert!(err.contains("broken"));
+        assert!(marker.exists());
+    }
+
+    #[test]
+    fn hook_may_ignore_its_input() {
+        let tmp = TempDir::new().unwrap();
+        let hooks = Hooks {
+            pre_steer: vec![script(&tmp, "noop", "exit 0")],
+            ..Hooks::default()
+        };
+
+        let (provenance, steer) = (provenance(), steer());
+        hooks
+            .run(&input(Event::PreSteer, &provenance, &steer))
+            .unwrap();
+    }
+
+    #[test]
+    fn hook_past_its_timeout_is_killed_and_fails() {
+        let tmp = TempDir::new().unwrap();
+        let marker = tmp.path().join("ran");
+        let hooks = Hooks {
+            pre_steer: vec![
+                script(&tmp, "stuck", "exec sleep 30"),
+                script(&tmp, "after", &format!("touch {}", marker.display())),
+            ],
+            timeout_secs: Some(1),
+            ..Hooks::default()
+        };
+
+        let (provenance, steer) = (provenance(), steer());
+        let started = Instant::now();
+        let err = hooks
+            .run(&input(Event::PreSteer, &provenance, &steer))
+            .unwrap_err();
+
+        assert!(err.contains("stuck timed out after 1s"), "{err}");
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert!(!marker.exists());
+    }
+
+    #[test]
+    fn missing_hook_is_an_error() {
+        let hooks = Hooks {
+            pre_steer: vec![PathBuf::from("/nonexistent/hook")],
+            ..Hooks::default()
+        };
+
+        let (provenance, steer) = (provenance(), steer());
+        let err = hooks
+            .run(&input(Event::PreSteer, &provenance, &steer))
+            .unwrap_err();
+        assert!(err.contains("failed to run hook /nonexistent/hook"));
+    }
+
+    #[test]
+    fn hooks_load_from_config() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("config.toml");
+        fs::write(
+            &path,
+            "identity = \"dyreby\"\n\n[hooks]\npre-steer = [\"/bin/lint\"]\npost-log = [\"audit\"]\ntimeout-secs = 5\n",
+        )
+        .unwrap();
+
+        let hooks = load_from(&path).unwrap();
+        assert_eq!(hooks.pre_steer, vec![PathBuf::from("/bin/lint")]);
+        assert_eq!(hooks.post_log, vec![PathBuf::from("audit")]);
+        assert!(hooks.post_steer.is_empty());
+        assert_eq!(hooks.timeout_secs, Some(5));
+    }
+
+    #[test]
+    fn missing_config_means_no_hooks() {
+        let tmp = TempDir::new().unwrap();
+        let hooks = load_from(&tmp.path().join("config.toml")).unwrap();
+        assert!(hooks.pre_steer.is_empty());
+    }
+
+    #[test]
+    fn unknown_hook_event_is_rejected() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("config.toml");
+        fs::write(&path, "[hooks]\npre-merge = [\"/bin/lint\"]\n").unwrap();
+        assert!(load_from(&path).unwrap_err().contains("failed to parse"));
+    }
+}
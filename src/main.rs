@@ -1,13 +1,17 @@
 mod bearing;
 mod cli;
+mod hooks;
 mod identity;
 mod model;
 mod observe;
+mod policy;
+mod signing;
 mod steer;
 mod storage;
 
 use std::process;
 
+use signing::Keys;
 use storage::Storage;
 
 fn main() {
@@ -17,7 +21,10 @@ fn main() {
     });
 
     let storage = match Storage::new(root) {
-        Ok(s) => s,
+        Ok(s) => match Keys::default_dir() {
+            Some(dir) => s.with_keys(Keys::new(dir)),
+            None => s,
+        },
         Err(e) => {
             eprintln!("Failed to initialize storage: {e}");
             process::exit(1);
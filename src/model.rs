@@ -21,7 +21,10 @@ pub use payload::{
     GitHubIssueSummary, GitHubPullRequestSummary, GitHubSummary, IssuePayload, Payload,
     PullRequestPayload, RepositoryPayload, ReviewComment,
 };
-pub use steer::{CommentTarget, Steer};
+pub use steer::{
+    CloseReason, CommentTarget, DiffSide, EditSnapshot, InlineComment, LockReason, MergeStrategy,
+    Reaction, ReactionTarget, ReviewVerdict, Steer, SteerOutcome, Subject, TriageSnapshot,
+};
 pub use voyage::{Voyage, VoyageStatus};
 
 /// A single entry in the logbook.
@@ -48,15 +51,157 @@ pub struct LogbookEntry {
 
     /// What happened.
     pub kind: EntryKind,
+
+    /// Who co-signed the steer, if it was proposed and approved by a second identity.
+    pub approval: Option<Approval>,
+}
+
+/// A second identity's sign-off on a proposed steer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Approval {
+    /// Who approved, in what framing, and how.
+    pub provenance: Provenance,
+
+    /// Why they approved.
+    pub summary: String,
+
+    /// When they approved.
+    pub approved_at: Timestamp,
+}
+
+/// A steer that was started or proposed but not yet recorded in the logbook.
+///
+/// Written before the action runs and removed once it is recorded or abandoned.
+/// One left performing after its invocation means helm was interrupted mid-steer:
+/// the action may or may not have happened.
+#[derive(Debug, Clone)]
+pub struct PendingSteer {
+    /// Journal row ID, used to reconcile it.
+    pub id: i64,
+
+    /// The action that was about to be performed.
+    pub steer: Steer,
+
+    /// The summary the steer was started with.
+    pub summary: String,
+
+    /// Who started the steer, in what framing, and how.
+    pub provenance: Provenance,
+
+    /// When the steer was started.
+    pub started_at: Timestamp,
+
+    /// How many observations were sealed into its bearing.
+    pub observation_count: usize,
+
+    /// Whether the action is underway or still awaiting approval.
+    pub status: PendingStatus,
+
+    /// The co-signature, once a proposal has been approved.
+    pub approval: Option<Approval>,
+}
+
+/// Where a pending steer is in its lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PendingStatus {
+    /// Proposed, waiting for a second identity to approve it.
+    Proposed,
+
+    /// Its action is being performed — or was, when helm was interrupted.
+    Performing,
+}
+
+/// A steer the policy refused before it was performed.
+#[derive(Debug, Clone)]
+pub struct Denial {
+    /// The action that was refused.
+    pub steer: Steer,
+
+    /// The summary the steer was attempted with.
+    pub summary: String,
+
+    /// Who attempted it — the approver, when the steer was a proposal.
+    pub provenance: Provenance,
+
+    /// Why the policy refused it.
+    pub reason: String,
+
+    /// When it was refused.
+    pub denied_at: Timestamp,
+}
+
+/// Who acted, in what framing, and how the thinking was done.
+///
+/// Recorded on every logbook entry. The three axes are orthogonal:
+/// identity is the external actor, role is the mindset, method is the engine.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Provenance {
+    /// Who acted.
+    pub identity: String,
+
+    /// What cognitive framing was adopted (e.g. `"reviewer"`, `"coder"`).
+    pub role: String,
+
+    /// How the thinking was done (e.g. `"claude-opus-4, thinking high"`, `"conversation"`).
+    pub method: String,
 }
 
 /// What kind of logbook entry this is.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "kind", content = "data", rename_all = "camelCase")]
+#[serde(
+    tag = "kind",
+    content = "data",
+    rename_all = "camelCase",
+    from = "StoredEntryKind"
+)]
 pub enum EntryKind {
     /// A steering action: mutated collaborative state.
-    Steer(Steer),
+    Steer {
+        /// The action that was performed.
+        steer: Steer,
+
+        /// What the action produced, if anything worth recording.
+        outcome: Option<SteerOutcome>,
+    },
 
     /// A logged state: recorded without mutation.
     Log(String),
 }
+
+/// Every shape an `EntryKind` has been stored in, so older logbooks still load.
+#[derive(Deserialize)]
+#[serde(tag = "kind", content = "data", rename_all = "camelCase")]
+enum StoredEntryKind {
+    Steer(StoredSteer),
+    Log(String),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredSteer {
+    /// The steer and what it produced.
+    WithOutcome {
+        steer: Steer,
+        outcome: Option<SteerOutcome>,
+    },
+
+    /// Entries recorded before outcomes were: the bare steer.
+    Bare(Steer),
+}
+
+impl From<StoredEntryKind> for EntryKind {
+    fn from(stored: StoredEntryKind) -> Self {
+        match stored {
+            StoredEntryKind::Steer(StoredSteer::WithOutcome { steer, outcome }) => {
+                Self::Steer { steer, outcome }
+            }
+            StoredEntryKind::Steer(StoredSteer::Bare(steer)) => Self::Steer {
+                steer,
+                outcome: None,
+            },
+            StoredEntryKind::Log(status) => Self::Log(status),
+        }
+    }
+}
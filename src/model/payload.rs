@@ -103,6 +103,9 @@ pub struct GitHubSummary {
     /// PR-specific: the base branch name.
     pub base_branch: Option<String>,
 
+    /// PR-specific: the head commit SHA at observation time.
+    pub head_sha: Option<String>,
+
     /// Body text.
     pub body: Option<String>,
 }
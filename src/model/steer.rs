@@ -1,5 +1,7 @@
 //! Steer: intent-based actions that mutate collaborative state.
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 /// Intent-based actions that mutate collaborative state.
@@ -22,28 +24,458 @@ pub enum Steer {
     },
 
     /// Create an issue.
-    CreateIssue,
+    CreateIssue {
+        /// Issue title.
+        title: String,
+
+        /// Issue body.
+        body: String,
+
+        /// Labels to apply on creation.
+        labels: Vec<String>,
+
+        /// Users to assign on creation.
+        assignees: Vec<String>,
+
+        /// Milestone to attach, by name.
+        milestone: Option<String>,
+    },
+
+    /// Update an issue's title, body, or labels.
+    ///
+    /// `None` and empty lists leave that field untouched.
+    EditIssue {
+        /// Issue number.
+        number: u64,
+
+        /// New title.
+        title: Option<String>,
+
+        /// New body.
+        body: Option<String>,
+
+        /// Labels to add.
+        add_labels: Vec<String>,
+
+        /// Labels to remove.
+        remove_labels: Vec<String>,
+    },
+
+    /// Add and remove labels on an issue or PR.
+    Label {
+        /// Issue or PR number.
+        number: u64,
+
+        /// Whether `number` is an issue or a PR.
+        subject: Subject,
+
+        /// Labels to add.
+        add: Vec<String>,
+
+        /// Labels to remove.
+        remove: Vec<String>,
+    },
+
+    /// Assign and unassign users on an issue or PR.
+    Assign {
+        /// Issue or PR number.
+        number: u64,
+
+        /// Whether `number` is an issue or a PR.
+        subject: Subject,
+
+        /// Users to assign.
+        add: Vec<String>,
+
+        /// Users to unassign.
+        remove: Vec<String>,
+    },
+
+    /// Set or clear the milestone on an issue or PR.
+    SetMilestone {
+        /// Issue or PR number.
+        number: u64,
 
-    /// Update issue title or body.
-    EditIssue,
+        /// Whether `number` is an issue or a PR.
+        subject: Subject,
+
+        /// Milestone to set, by name. `None` clears it.
+        milestone: Option<String>,
+    },
 
     /// Close an issue.
-    CloseIssue,
+    CloseIssue {
+        /// Issue number.
+        number: u64,
+
+        /// Why the issue is being closed.
+        reason: CloseReason,
+
+        /// Comment posted just before closing.
+        comment: Option<String>,
+    },
+
+    /// Reopen a closed issue.
+    ReopenIssue {
+        /// Issue number.
+        number: u64,
+
+        /// Comment posted alongside reopening.
+        comment: Option<String>,
+    },
 
     /// Create a pull request.
-    CreatePullRequest,
+    CreatePullRequest {
+        /// Branch containing the changes.
+        head: String,
+
+        /// Branch the changes should merge into.
+        base: String,
+
+        /// PR title.
+        title: String,
 
-    /// Update PR title or body.
-    EditPullRequest,
+        /// PR body.
+        body: String,
+
+        /// Open as a draft.
+        draft: bool,
+
+        /// Users to request review from on creation.
+        reviewers: Vec<String>,
+    },
+
+    /// Update a PR's title, body, or labels.
+    ///
+    /// `None` and empty lists leave that field untouched.
+    EditPullRequest {
+        /// PR number.
+        number: u64,
+
+        /// New title.
+        title: Option<String>,
+
+        /// New body.
+        body: Option<String>,
+
+        /// Labels to add.
+        add_labels: Vec<String>,
+
+        /// Labels to remove.
+        remove_labels: Vec<String>,
+    },
 
     /// Close a PR without merging.
-    ClosePullRequest,
+    ClosePullRequest {
+        /// PR number.
+        number: u64,
+
+        /// Comment posted just before closing.
+        comment: Option<String>,
+    },
+
+    /// Reopen a closed PR.
+    ReopenPullRequest {
+        /// PR number.
+        number: u64,
+
+        /// Comment posted alongside reopening.
+        comment: Option<String>,
+    },
+
+    /// Mark a draft PR ready for review.
+    MarkReadyForReview {
+        /// PR number.
+        number: u64,
+    },
+
+    /// Convert a PR back to a draft.
+    ConvertToDraft {
+        /// PR number.
+        number: u64,
+    },
+
+    /// Bring a PR's head branch up to date with its base.
+    UpdateBranch {
+        /// PR number.
+        number: u64,
+
+        /// Rebase the head onto the base instead of merging the base in.
+        rebase: bool,
+    },
 
     /// Request reviewers on a PR.
-    RequestReview,
+    RequestReview {
+        /// PR number.
+        number: u64,
+
+        /// Users to request review from.
+        reviewers: Vec<String>,
+
+        /// Team slugs to request review from.
+        teams: Vec<String>,
+    },
+
+    /// Withdraw pending review requests on a PR.
+    WithdrawReviewRequest {
+        /// PR number.
+        number: u64,
+
+        /// Users whose review requests are withdrawn.
+        reviewers: Vec<String>,
+
+        /// Team slugs whose review requests are withdrawn.
+        teams: Vec<String>,
+    },
+
+    /// Submit a full review on a PR: a verdict, a summary, and new inline comments.
+    ///
+    /// Posts as one review. Replies to existing threads go through `Comment`
+    /// with `CommentTarget::ReviewFeedback` instead.
+    SubmitReview {
+        /// PR number.
+        number: u64,
+
+        /// The review's verdict.
+        verdict: ReviewVerdict,
+
+        /// Summary body of the review.
+        body: String,
+
+        /// New inline comments posted as part of the review.
+        comments: Vec<InlineComment>,
+    },
+
+    /// Resolve the review thread holding a review comment.
+    ResolveThread {
+        /// PR number.
+        number: u64,
+
+        /// Any review comment in the thread — the same ID `CommentTarget::ReviewFeedback` takes.
+        comment_id: u64,
+    },
+
+    /// Unresolve the review thread holding a review comment.
+    UnresolveThread {
+        /// PR number.
+        number: u64,
+
+        /// Any review comment in the thread.
+        comment_id: u64,
+    },
+
+    /// React to a comment on an issue or PR.
+    React {
+        /// Issue or PR number the comment is on. Checked against the comment before acting.
+        number: u64,
+
+        /// Whether `number` is an issue or a PR.
+        subject: Subject,
+
+        /// The comment reacted to.
+        target: ReactionTarget,
+
+        /// The reaction to add.
+        reaction: Reaction,
+    },
+
+    /// Remove one's own reaction from a comment on an issue or PR.
+    Unreact {
+        /// Issue or PR number the comment is on. Checked against the comment before acting.
+        number: u64,
+
+        /// Whether `number` is an issue or a PR.
+        subject: Subject,
+
+        /// The comment reacted to.
+        target: ReactionTarget,
+
+        /// The reaction to remove.
+        reaction: Reaction,
+    },
+
+    /// Lock the conversation on an issue or PR.
+    Lock {
+        /// Issue or PR number.
+        number: u64,
+
+        /// Whether `number` is an issue or a PR.
+        subject: Subject,
+
+        /// Why the conversation is locked. `None` gives no reason.
+        reason: Option<LockReason>,
+    },
+
+    /// Unlock the conversation on an issue or PR.
+    Unlock {
+        /// Issue or PR number.
+        number: u64,
+
+        /// Whether `number` is an issue or a PR.
+        subject: Subject,
+    },
+
+    /// Re-run the failed jobs of every failed workflow run on a PR's head.
+    RerunChecks {
+        /// PR number.
+        number: u64,
+    },
+
+    /// Trigger a workflow's `workflow_dispatch` event.
+    DispatchWorkflow {
+        /// Workflow file name (e.g. `deploy.yml`), ID, or name.
+        workflow: String,
+
+        /// Branch or tag to run on. `None` uses the default branch.
+        git_ref: Option<String>,
+
+        /// Workflow inputs by name.
+        inputs: BTreeMap<String, String>,
+    },
+
+    /// Create a release, tagging `target` if the tag doesn't exist yet.
+    CreateRelease {
+        /// Tag to release.
+        tag: String,
+
+        /// Branch or commit to tag. `None` uses the default branch.
+        target: Option<String>,
+
+        /// Release title.
+        title: String,
+
+        /// Release notes.
+        notes: String,
+
+        /// Save as a draft instead of publishing.
+        draft: bool,
+
+        /// Mark as a prerelease.
+        prerelease: bool,
+    },
 
     /// Merge a PR.
-    MergePullRequest,
+    ///
+    /// Guarded: refuses to merge unless the PR head still matches `expected_head`.
+    MergePullRequest {
+        /// PR number.
+        number: u64,
+
+        /// How the PR's commits land on the base branch.
+        strategy: MergeStrategy,
+
+        /// Delete the head branch after merging.
+        delete_branch: bool,
+
+        /// The head commit the merge decision was made against.
+        expected_head: String,
+    },
+}
+
+impl Steer {
+    /// Every name [`Steer::name`] can return.
+    pub const NAMES: &[&str] = &[
+        "comment",
+        "create-issue",
+        "edit-issue",
+        "label",
+        "assign",
+        "milestone",
+        "close-issue",
+        "reopen-issue",
+        "create-pr",
+        "edit-pr",
+        "close-pr",
+        "reopen-pr",
+        "ready-pr",
+        "draft-pr",
+        "update-branch",
+        "request-review",
+        "withdraw-review-request",
+        "submit-review",
+        "resolve-thread",
+        "unresolve-thread",
+        "react",
+        "unreact",
+        "lock",
+        "unlock",
+        "rerun-checks",
+        "dispatch-workflow",
+        "create-release",
+        "merge-pr",
+    ];
+
+    /// The variant's name as policies refer to it, e.g. `merge-pr`.
+    ///
+    /// Matches the steer subcommand where there is one; the variants a
+    /// subcommand reaches with a flag (`--reopen`, `--remove`) get their own.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Comment { .. } => "comment",
+            Self::CreateIssue { .. } => "create-issue",
+            Self::EditIssue { .. } => "edit-issue",
+            Self::Label { .. } => "label",
+            Self::Assign { .. } => "assign",
+            Self::SetMilestone { .. } => "milestone",
+            Self::CloseIssue { .. } => "close-issue",
+            Self::ReopenIssue { .. } => "reopen-issue",
+            Self::CreatePullRequest { .. } => "create-pr",
+            Self::EditPullRequest { .. } => "edit-pr",
+            Self::ClosePullRequest { .. } => "close-pr",
+            Self::ReopenPullRequest { .. } => "reopen-pr",
+            Self::MarkReadyForReview { .. } => "ready-pr",
+            Self::ConvertToDraft { .. } => "draft-pr",
+            Self::UpdateBranch { .. } => "update-branch",
+            Self::RequestReview { .. } => "request-review",
+            Self::WithdrawReviewRequest { .. } => "withdraw-review-request",
+            Self::SubmitReview { .. } => "submit-review",
+            Self::ResolveThread { .. } => "resolve-thread",
+            Self::UnresolveThread { .. } => "unresolve-thread",
+            Self::React { .. } => "react",
+            Self::Unreact { .. } => "unreact",
+            Self::Lock { .. } => "lock",
+            Self::Unlock { .. } => "unlock",
+            Self::RerunChecks { .. } => "rerun-checks",
+            Self::DispatchWorkflow { .. } => "dispatch-workflow",
+            Self::CreateRelease { .. } => "create-release",
+            Self::MergePullRequest { .. } => "merge-pr",
+        }
+    }
+
+    /// The issue or PR number the steer acts on.
+    /// `None` for steers that create one or don't act on one.
+    pub fn number(&self) -> Option<u64> {
+        match self {
+            Self::CreateIssue { .. }
+            | Self::CreatePullRequest { .. }
+            | Self::DispatchWorkflow { .. }
+            | Self::CreateRelease { .. } => None,
+            Self::Comment { number, .. }
+            | Self::EditIssue { number, .. }
+            | Self::Label { number, .. }
+            | Self::Assign { number, .. }
+            | Self::SetMilestone { number, .. }
+            | Self::CloseIssue { number, .. }
+            | Self::ReopenIssue { number, .. }
+            | Self::EditPullRequest { number, .. }
+            | Self::ClosePullRequest { number, .. }
+            | Self::ReopenPullRequest { number, .. }
+            | Self::MarkReadyForReview { number }
+            | Self::ConvertToDraft { number }
+            | Self::UpdateBranch { number, .. }
+            | Self::RequestReview { number, .. }
+            | Self::WithdrawReviewRequest { number, .. }
+            | Self::SubmitReview { number, .. }
+            | Self::ResolveThread { number, .. }
+            | Self::UnresolveThread { number, .. }
+            | Self::React { number, .. }
+            | Self::Unreact { number, .. }
+            | Self::Lock { number, .. }
+            | Self::Unlock { number, .. }
+            | Self::RerunChecks { number }
+            | Self::MergePullRequest { number, .. } => Some(*number),
+        }
+    }
 }
 
 /// Where a comment lands — routes to the correct `gh` command.
@@ -62,3 +494,334 @@ pub enum CommentTarget {
         comment_id: u64,
     },
 }
+
+/// Whether a number refers to an issue or a pull request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Subject {
+    Issue,
+    PullRequest,
+}
+
+/// The comment a reaction is on.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ReactionTarget {
+    /// A comment in an issue's or PR's conversation.
+    Comment {
+        /// The comment's ID.
+        comment_id: u64,
+    },
+
+    /// An inline review comment on a PR.
+    ReviewComment {
+        /// The review comment's ID — the same ID `CommentTarget::ReviewFeedback` takes.
+        comment_id: u64,
+    },
+}
+
+/// An emoji reaction, as GitHub names its content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Reaction {
+    /// 👍
+    #[serde(rename = "+1")]
+    ThumbsUp,
+
+    /// 👎
+    #[serde(rename = "-1")]
+    ThumbsDown,
+
+    /// 😄
+    Laugh,
+
+    /// 😕
+    Confused,
+
+    /// ❤️
+    Heart,
+
+    /// 🎉
+    Hooray,
+
+    /// 🚀
+    Rocket,
+
+    /// 👀
+    Eyes,
+}
+
+impl Reaction {
+    /// GitHub's name for the reaction, e.g. `+1` or `rocket`.
+    pub fn content(self) -> &'static str {
+        match self {
+            Self::ThumbsUp => "+1",
+            Self::ThumbsDown => "-1",
+            Self::Laugh => "laugh",
+            Self::Confused => "confused",
+            Self::Heart => "heart",
+            Self::Hooray => "hooray",
+            Self::Rocket => "rocket",
+            Self::Eyes => "eyes",
+        }
+    }
+}
+
+/// Why a conversation was locked — GitHub's `lock_reason`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LockReason {
+    /// The conversation strayed from the issue or PR.
+    OffTopic,
+
+    /// The conversation has run its course.
+    Resolved,
+
+    /// The conversation is drawing spam.
+    Spam,
+
+    /// The conversation needs to cool down.
+    TooHeated,
+}
+
+/// The verdict of a submitted review.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReviewVerdict {
+    /// Approve the changes.
+    Approve,
+
+    /// Block the merge until the feedback is addressed.
+    RequestChanges,
+
+    /// Leave feedback without a verdict.
+    Comment,
+}
+
+/// A new inline comment anchored to lines of a PR diff.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InlineComment {
+    /// File path relative to the repository root.
+    pub path: String,
+
+    /// The line the comment is anchored to (the last line of a range).
+    pub line: u64,
+
+    /// First line of a multi-line range. `None` for a single line.
+    pub start_line: Option<u64>,
+
+    /// Which side of the diff the lines refer to.
+    pub side: DiffSide,
+
+    /// Comment body.
+    pub body: String,
+}
+
+/// Which side of a diff a line number refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DiffSide {
+    /// The base version: deleted or unchanged lines.
+    Left,
+
+    /// The head version: added or unchanged lines.
+    Right,
+}
+
+/// Why an issue was closed — GitHub's `state_reason`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CloseReason {
+    /// The work is done.
+    Completed,
+
+    /// The work won't be done.
+    NotPlanned,
+
+    /// Another issue already tracks this.
+    Duplicate,
+}
+
+/// How a PR's commits land on the base branch.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MergeStrategy {
+    /// Create a merge commit.
+    Merge,
+
+    /// Squash all commits into one.
+    Squash,
+
+    /// Rebase commits onto the base branch.
+    Rebase,
+}
+
+/// What a steer produced in the world.
+///
+/// Recorded alongside the steer so later entries can refer to
+/// exactly what an earlier action created.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum SteerOutcome {
+    /// A comment was posted — on its own, or alongside a state change.
+    CommentPosted {
+        /// The new comment's ID. Usable as `--reply-to` for review threads.
+        id: u64,
+
+        /// The new comment's URL.
+        url: String,
+    },
+
+    /// A new issue was opened.
+    IssueCreated {
+        /// The new issue's number.
+        number: u64,
+
+        /// The new issue's URL.
+        url: String,
+    },
+
+    /// A new pull request was opened.
+    PullRequestCreated {
+        /// The new PR's number.
+        number: u64,
+
+        /// The new PR's URL.
+        url: String,
+
+        /// Head commit at creation time. `None` if it couldn't be read back.
+        head_sha: Option<String>,
+
+        /// Base commit at creation time. `None` if it couldn't be read back.
+        base_sha: Option<String>,
+    },
+
+    /// An issue or PR was edited.
+    ///
+    /// Both snapshots are read from GitHub: `before` just ahead of the edit,
+    /// `after` once it landed. The logbook shows exactly what text was replaced.
+    /// Boxed to keep variant sizes balanced.
+    Edited {
+        /// Title, body, and labels just before the edit.
+        before: Box<EditSnapshot>,
+
+        /// Title, body, and labels after the edit. `None` if they couldn't be read back.
+        after: Option<Box<EditSnapshot>>,
+    },
+
+    /// An issue's or PR's labels, assignees, or milestone changed.
+    ///
+    /// Read from GitHub just ahead of the change and once it landed, like `Edited`.
+    /// Boxed to keep variant sizes balanced.
+    Triaged {
+        /// Labels, assignees, and milestone just before the change.
+        before: Box<TriageSnapshot>,
+
+        /// Labels, assignees, and milestone after the change.
+        /// `None` if they couldn't be read back.
+        after: Option<Box<TriageSnapshot>>,
+    },
+
+    /// Review requests on a PR changed.
+    ReviewRequests {
+        /// Users with a pending review request after the change.
+        reviewers: Vec<String>,
+
+        /// Team slugs with a pending review request after the change.
+        teams: Vec<String>,
+    },
+
+    /// A review was submitted.
+    ReviewSubmitted {
+        /// The review's ID.
+        id: u64,
+
+        /// The review's URL.
+        url: String,
+    },
+
+    /// A review thread was resolved or unresolved.
+    ThreadUpdated {
+        /// The thread's GraphQL node ID.
+        thread_id: String,
+    },
+
+    /// A reaction was added to a comment.
+    Reacted {
+        /// The reaction's ID: the one added, or the one already there.
+        reaction_id: u64,
+    },
+
+    /// A reaction was removed from a comment.
+    Unreacted {
+        /// The removed reaction's ID.
+        reaction_id: u64,
+    },
+
+    /// Failed workflow runs on a PR's head were re-run.
+    ChecksRerun {
+        /// The head commit whose runs were re-run.
+        head_sha: String,
+
+        /// IDs of the workflow runs re-run.
+        run_ids: Vec<u64>,
+    },
+
+    /// A release was created.
+    ReleaseCreated {
+        /// The release's tag.
+        tag: String,
+
+        /// The release's URL.
+        url: String,
+    },
+
+    /// A PR's head branch was brought up to date with its base.
+    BranchUpdated {
+        /// The PR's head commit after the update. `None` if it couldn't be read back.
+        head_sha: Option<String>,
+    },
+
+    /// A pull request was merged.
+    PullRequestMerged {
+        /// The commit the merge produced on the base branch.
+        /// `None` if it couldn't be read back.
+        merge_commit_sha: Option<String>,
+    },
+
+    /// The action reached GitHub, then something failed: a later call it
+    /// makes, or reading back what it produced.
+    Incomplete {
+        /// What is known to have landed, if it produced anything.
+        landed: Option<Box<SteerOutcome>>,
+
+        /// What failed once the change had landed.
+        error: String,
+    },
+
+    /// Recorded by reconciliation after helm was interrupted mid-steer.
+    ///
+    /// Someone confirmed the action took effect; what it produced was not captured.
+    Reconciled,
+}
+
+/// The editable fields of an issue or PR at one moment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EditSnapshot {
+    pub title: String,
+    pub body: String,
+    pub labels: Vec<String>,
+}
+
+/// The triage fields of an issue or PR at one moment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TriageSnapshot {
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+
+    /// Milestone title, if one is set.
+    pub milestone: Option<String>,
+}
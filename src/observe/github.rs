@@ -86,6 +86,7 @@ struct GhPrView {
     assignees: Vec<GhActor>,
     head_ref_name: String,
     base_ref_name: String,
+    head_ref_oid: String,
     body: String,
 }
 
@@ -107,7 +108,7 @@ fn fetch_pr_summary(number: u64, gh_config: &Path) -> Option<GitHubSummary> {
             "view",
             &num,
             "--json",
-            "title,number,state,author,labels,assignees,headRefName,baseRefName,body",
+            "title,number,state,author,labels,assignees,headRefName,baseRefName,headRefOid,body",
         ],
         gh_config,
     )?;
@@ -123,6 +124,7 @@ fn fetch_pr_summary(number: u64, gh_config: &Path) -> Option<GitHubSummary> {
         assignees: pr.assignees.into_iter().map(|a| a.login).collect(),
         head_branch: Some(pr.head_ref_name),
         base_branch: Some(pr.base_ref_name),
+        head_sha: Some(pr.head_ref_oid),
         body: if pr.body.is_empty() {
             None
         } else {
@@ -309,6 +311,7 @@ fn fetch_issue_summary(number: u64, gh_config: &Path) -> Option<GitHubSummary> {
         assignees: issue.assignees.into_iter().map(|a| a.login).collect(),
         head_branch: None,
         base_branch: None,
+        head_sha: None,
         body: if issue.body.is_empty() {
             None
         } else {
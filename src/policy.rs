@@ -0,0 +1,283 @@
+//! Steer policy: the envelope each identity and role may act within.
+//!
+//! `~/.helm/policy.toml` lists what is allowed; anything it doesn't allow is denied.
+//! Without the file there is no envelope and every steer is allowed.
+//!
+//! ```toml
+//! # agent-x may comment, but never merge.
+//! [[allow]]
+//! identity = "agent-x"
+//! steers = ["comment"]
+//!
+//! # Anyone reviewing may review PR 42 in dyreby/helm.
+//! [[allow]]
+//! role = "reviewer"
+//! steers = ["submit-review", "comment"]
+//! repos = ["dyreby/helm"]
+//! targets = [42]
+//!
+//! [[allow]]
+//! identity = "dyreby"
+//! steers = ["*"]
+//! ```
+//!
+//! A rule applies when its `identity` and `role` match (an omitted field
+//! matches any). It allows a steer when the steer's name is in `steers`
+//! (`"*"` for all), its repository is in `repos`, and the issue or PR it
+//! acts on is in `targets`. Omitted `repos` or `targets` don't restrict;
+//! steers that create an issue or PR have no target, so a rule with
+//! `targets` never allows them.
+
+use std::{fs, path::Path};
+
+use serde::Deserialize;
+
+use crate::model::Steer;
+
+/// A parsed `policy.toml`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Policy {
+    #[serde(default)]
+    allow: Vec<Rule>,
+}
+
+/// One `[[allow]]` table.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Rule {
+    identity: Option<String>,
+    role: Option<String>,
+    steers: Vec<String>,
+    repos: Option<Vec<String>>,
+    targets: Option<Vec<u64>>,
+}
+
+/// Load `~/.helm/policy.toml`, or `None` if there isn't one.
+pub fn load_policy() -> Result<Option<Policy>, String> {
+    let Some(home) = dirs::home_dir() else {
+        return Ok(None);
+    };
+    load_from(&home.join(".helm").join("policy.toml"))
+}
+
+/// Inner loading logic — separated from environment I/O for testability.
+fn load_from(path: &Path) -> Result<Option<Policy>, String> {
+    let contents = match fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("failed to read {}: {e}", path.display())),
+    };
+    parse(&contents)
+        .map_err(|e| format!("failed to parse {}: {e}", path.display()))
+        .map(Some)
+}
+
+/// Parse a policy, rejecting steer names helm doesn't know so a typo can't
+/// silently allow nothing.
+fn parse(contents: &str) -> Result<Policy, String> {
+    let policy: Policy = toml::from_str(contents).map_err(|e| e.to_string())?;
+    for name in policy.allow.iter().flat_map(|rule| &rule.steers) {
+        if name != "*" && !Steer::NAMES.contains(&name.as_str()) {
+            return Err(format!(
+                "unknown steer \"{name}\" (expected \"*\" or one of: {})",
+                Steer::NAMES.join(", ")
+            ));
+        }
+    }
+    Ok(policy)
+}
+
+impl Policy {
+    /// Whether any rule that could apply to `identity` as `role` limits repositories.
+    ///
+    /// Finding the repository means asking `gh`, so callers only resolve it when this says so.
+    pub fn needs_repo(&self, identity: &str, role: &str) -> bool {
+        self.rules_for(identity, role)
+            .any(|rule| rule.repos.is_some())
+    }
+
+    /// Check a steer by `identity` as `role` against `repo`.
+    ///
+    /// `repo` is `owner/name`, or `None` if it wasn't resolved — then rules
+    /// that limit repositories don't allow anything. Returns why the steer
+    /// is denied.
+    pub fn check(
+        &self,
+        steer: &Steer,
+        identity: &str,
+        role: &str,
+        repo: Option<&str>,
+    ) -> Result<(), String> {
+        let name = steer.name();
+        let number = steer.number();
+        let allowed = self.rules_for(identity, role).any(|rule| {
+            rule.steers.iter().any(|s| s == "*" || s == name)
+                && rule
+                    .repos
+                    .as_ref()
+                    .is_none_or(|repos| repo.is_some_and(|r| repos.iter().any(|x| x == r)))
+                && rule
+                    .targets
+                    .as_ref()
+                    .is_none_or(|targets| number.is_some_and(|n| targets.contains(&n)))
+        });
+        if allowed {
+            return Ok(());
+        }
+
+        let target = number.map(|n| format!(" #{n}")).unwrap_or_default();
+        let place = repo.map(|r| format!(" in {r}")).unwrap_or_default();
+        Err(format!(
+            "policy does not allow {identity} as {role} to {name}{target}{place}"
+        ))
+    }
+
+    fn rules_for(&self, identity: &str, role: &str) -> impl Iterator<Item = &Rule> {
+        self.allow.iter().filter(move |rule| {
+            rule.identity.as_deref().is_none_or(|i| i == identity)
+                && rule.role.as_deref().is_none_or(|r| r == role)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::TempDir;
+
+    use crate::model::{CommentTarget, MergeStrategy};
+
+    fn comment(number: u64) -> Steer {
+        Steer::Comment {
+            number,
+            body: "LGTM".into(),
+            target: CommentTarget::PullRequest,
+        }
+    }
+
+    fn merge(number: u64) -> Steer {
+        Steer::MergePullRequest {
+            number,
+            strategy: MergeStrategy::Squash,
+            delete_branch: false,
+            expected_head: "abc123".into(),
+        }
+    }
+
+    fn create_issue() -> Steer {
+        Steer::CreateIssue {
+            title: "Bug".into(),
+            body: String::new(),
+            labels: vec![],
+            assignees: vec![],
+            milestone: None,
+        }
+    }
+
+    #[test]
+    fn identity_may_comment_but_never_merge() {
+        let policy = parse("[[allow]]\nidentity = \"agent-x\"\nsteers = [\"comment\"]\n").unwrap();
+
+        assert!(policy.check(&comment(7), "agent-x", "coder", None).is_ok());
+        let err = policy
+            .check(&merge(7), "agent-x", "coder", None)
+            .unwrap_err();
+        assert_eq!(err, "policy does not allow agent-x as coder to merge-pr #7");
+    }
+
+    #[test]
+    fn identities_without_a_rule_are_denied() {
+        let policy = parse("[[allow]]\nidentity = \"agent-x\"\nsteers = [\"*\"]\n").unwrap();
+        assert!(policy.check(&comment(7), "agent-y", "coder", None).is_err());
+    }
+
+    #[test]
+    fn wildcard_allows_every_steer() {
+        let policy = parse("[[allow]]\nidentity = \"dyreby\"\nsteers = [\"*\"]\n").unwrap();
+        assert!(policy.check(&merge(7), "dyreby", "coder", None).is_ok());
+        assert!(
+            policy
+                .check(&create_issue(), "dyreby", "coder", None)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn role_rules_apply_to_any_identity_in_that_role() {
+        let policy = parse("[[allow]]\nrole = \"reviewer\"\nsteers = [\"comment\"]\n").unwrap();
+        assert!(
+            policy
+                .check(&comment(7), "anyone", "reviewer", None)
+                .is_ok()
+        );
+        assert!(policy.check(&comment(7), "anyone", "coder", None).is_err());
+    }
+
+    #[test]
+    fn repos_limit_where_a_rule_applies() {
+        let policy = parse(
+            "[[allow]]\nidentity = \"agent-x\"\nsteers = [\"comment\"]\nrepos = [\"dyreby/helm\"]\n",
+        )
+        .unwrap();
+
+        assert!(policy.needs_repo("agent-x", "coder"));
+        assert!(!policy.needs_repo("agent-y", "coder"));
+        assert!(
+            policy
+                .check(&comment(7), "agent-x", "coder", Some("dyreby/helm"))
+                .is_ok()
+        );
+        let err = policy
+            .check(&comment(7), "agent-x", "coder", Some("dyreby/other"))
+            .unwrap_err();
+        assert!(err.ends_with("comment #7 in dyreby/other"));
+        assert!(policy.check(&comment(7), "agent-x", "coder", None).is_err());
+    }
+
+    #[test]
+    fn targets_limit_which_numbers_a_rule_allows() {
+        let policy =
+            parse("[[allow]]\nidentity = \"agent-x\"\nsteers = [\"*\"]\ntargets = [42]\n").unwrap();
+
+        assert!(policy.check(&comment(42), "agent-x", "coder", None).is_ok());
+        assert!(policy.check(&comment(7), "agent-x", "coder", None).is_err());
+        assert!(
+            policy
+                .check(&create_issue(), "agent-x", "coder", None)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn unknown_steer_name_is_rejected() {
+        let err = parse("[[allow]]\nsteers = [\"merge-pull-request\"]\n").unwrap_err();
+        assert!(err.contains("unknown steer \"merge-pull-request\""));
+    }
+
+    #[test]
+    fn unknown_field_is_rejected() {
+        assert!(parse("[[allow]]\nsteers = [\"*\"]\ndeny = [\"merge-pr\"]\n").is_err());
+    }
+
+    #[test]
+    fn missing_policy_file_is_no_policy() {
+        let tmp = TempDir::new().unwrap();
+        assert!(
+            load_from(&tmp.path().join("policy.toml"))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn empty_policy_file_denies_everything() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("policy.toml");
+        std::fs::write(&path, "").unwrap();
+
+        let policy = load_from(&path).unwrap().unwrap();
+        assert!(policy.check(&comment(7), "dyreby", "coder", None).is_err());
+    }
+}
@@ -0,0 +1,245 @@
+//! Signing keys: optional per-identity keys that sign logbook entries.
+//!
+//! An identity's key is an OpenSSH private key at `~/.helm/keys/<identity>`,
+//! with its public half beside it at `<identity>.pub`:
+//!
+//! ```text
+//! ssh-keygen -t ed25519 -N "" -C dyreby -f ~/.helm/keys/dyreby
+//! ```
+//!
+//! Entries recorded by an identity with a key are signed with
+//! `ssh-keygen -Y sign` under the `helm-logbook` namespace, and
+//! `helm logbook verify` checks them with `ssh-keygen -Y verify` against the
+//! public key. Identities without a key record unsigned entries.
+//!
+//! Signing runs non-interactively, so the key must not need a passphrase;
+//! keep it readable only by whoever acts as that identity.
+
+use std::{
+    fs, io,
+    io::Write as _,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+};
+
+use uuid::Uuid;
+
+/// The `ssh-keygen -Y` namespace, so a logbook signature can't pass for any other.
+const NAMESPACE: &str = "helm-logbook";
+
+/// Where signing keys are kept.
+#[derive(Debug, Clone)]
+pub struct Keys {
+    dir: PathBuf,
+}
+
+/// The outcome of checking a signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// Made by the identity's key over exactly this data.
+    Good,
+    /// Not made by the identity's key, or not over this data.
+    Bad,
+    /// The identity has no public key to check against.
+    NoKey,
+}
+
+impl Keys {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Returns the default key directory: `~/.helm/keys/`.
+    pub fn default_dir() -> Option<PathBuf> {
+        dirs::home_dir().map(|h| h.join(".helm").join("keys"))
+    }
+
+    fn private_key(&self, identity: &str) -> Result<PathBuf, String> {
+        check_identity(identity)?;
+        Ok(self.dir.join(identity))
+    }
+
+    fn public_key(&self, identity: &str) -> Result<PathBuf, String> {
+        check_identity(identity)?;
+        Ok(self.dir.join(format!("{identity}.pub")))
+    }
+
+    /// Whether `identity` has a public key to check its signatures against.
+    pub fn has_public_key(&self, identity: &str) -> Result<bool, String> {
+        Ok(self.public_key(identity)?.exists())
+    }
+
+    /// Sign `data` as `identity`, returning the armored signature.
+    ///
+    /// Returns `None` when the identity has no key.
+    pub fn sign(&self, identity: &str, data: &[u8]) -> Result<Option<String>, String> {
+        let key = self.private_key(identity)?;
+        if !key.exists() {
+            return Ok(None);
+        }
+        let output = ssh_keygen(
+            &[
+                "-Y".as_ref(),
+                "sign".as_ref(),
+                "-f".as_ref(),
+                key.as_os_str(),
+                "-n".as_ref(),
+                NAMESPACE.as_ref(),
+            ],
+            data,
+        )?;
+        if !output.status.success() {
+            return Err(format!(
+                "failed to sign as {identity} with {}: {}",
+                key.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        String::from_utf8(output.stdout)
+            .map(Some)
+            .map_err(|e| format!("ssh-keygen produced a malformed signature: {e}"))
+    }
+
+    /// Check that `signature` was made by `identity`'s key over `data`.
+    pub fn verify(&self, identity: &str, data: &[u8], signature: &str) -> Result<Verdict, String> {
+        let path = self.public_key(identity)?;
+        let public_key = match fs::read_to_string(&path) {
+            Ok(key) => key,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Verdict::NoKey),
+            Err(e) => return Err(format!("failed to read {}: {e}", path.display())),
+        };
+
+        // `-Y verify` reads the signature and the trusted keys from files.
+        let scratch = std::env::temp_dir().join(format!("helm-verify-{}", Uuid::new_v4()));
+        fs::create_dir(&scratch)
+            .map_err(|e| format!("failed to create {}: {e}", scratch.display()))?;
+        let result = verify_in(&scratch, identity, public_key.trim(), data, signature);
+        // Best-effort cleanup; the files hold nothing secret.
+        let _ = fs::remove_dir_all(&scratch);
+        result
+    }
+}
+
+/// Refuse identities that would name a key outside the key directory.
+fn check_identity(identity: &str) -> Result<(), String> {
+    if identity.is_empty() || identity.contains(['/', '\\']) || identity.contains("..") {
+        return Err(format!(
+            "identity {identity:?} can't name a signing key: \
+             it must not be empty or contain a path separator or `..`"
+        ));
+    }
+    Ok(())
+}
+
+fn verify_in(
+    scratch: &Path,
+    identity: &str,
+    public_key: &str,
+    data: &[u8],
+    signature: &str,
+) -> Result<Verdict, String> {
+    let allowed_signers = scratch.join("allowed_signers");
+    let signature_file = scratch.join("signature");
+    fs::write(&allowed_signers, format!("{identity} {public_key}\n"))
+        .and_then(|()| fs::write(&signature_file, signature))
+        .map_err(|e| format!("failed to write to {}: {e}", scratch.display()))?;
+
+    let output = ssh_keygen(
+        &[
+            "-Y".as_ref(),
+            "verify".as_ref(),
+            "-f".as_ref(),
+            allowed_signers.as_os_str(),
+            "-I".as_ref(),
+            identity.as_ref(),
+            "-n".as_ref(),
+            NAMESPACE.as_ref(),
+            "-s".as_ref(),
+            signature_file.as_os_str(),
+        ],
+        data,
+    )?;
+    Ok(if output.status.success() {
+        Verdict::Good
+    } else {
+        Verdict::Bad
+    })
+}
+
+/// Run `ssh-keygen` with `input` on stdin.
+fn ssh_keygen(args: &[&std::ffi::OsStr], input: &[u8]) -> Result<Output, String> {
+    let mut child = Command::new("ssh-keygen")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run ssh-keygen: {e}"))?;
+    child
+        .stdin
+        .take()
+        .ok_or("failed to open stdin of ssh-keygen")?
+        .write_all(input)
+        .map_err(|e| format!("failed to write to ssh-keygen: {e}"))?;
+    child
+        .wait_with_output()
+        .map_err(|e| format!("failed to run ssh-keygen: {e}"))
+}
+
+/// A key directory holding a fresh key for `identity`.
+///
+/// Panics without `ssh-keygen`: signing can't be tested without it.
+#[cfg(test)]
+pub(crate) fn keys_for(identity: &str) -> (tempfile::TempDir, Keys) {
+    let dir = tempfile::TempDir::new().unwrap();
+    let status = Command::new("ssh-keygen")
+        .args(["-q", "-t", "ed25519", "-N", "", "-C", identity, "-f"])
+        .arg(dir.path().join(identity))
+        .status()
+        .expect("signing tests need ssh-keygen on PATH");
+    assert!(status.success());
+    let keys = Keys::new(dir.path());
+    (dir, keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::TempDir;
+
+    #[test]
+    fn signature_verifies_only_over_the_signed_data() {
+        let (_dir, keys) = keys_for("alice");
+        let signature = keys.sign("alice", b"entry").unwrap().unwrap();
+        assert_eq!(
+            keys.verify("alice", b"entry", &signature).unwrap(),
+            Verdict::Good
+        );
+        assert_eq!(
+            keys.verify("alice", b"forged", &signature).unwrap(),
+            Verdict::Bad
+        );
+    }
+
+    #[test]
+    fn identity_without_a_key_neither_signs_nor_verifies() {
+        let dir = TempDir::new().unwrap();
+        let keys = Keys::new(dir.path());
+        assert!(keys.sign("bob", b"entry").unwrap().is_none());
+        assert_eq!(keys.verify("bob", b"entry", "sig").unwrap(), Verdict::NoKey);
+    }
+
+    #[test]
+    fn identities_cannot_reach_outside_the_key_directory() {
+        let dir = TempDir::new().unwrap();
+        let keys = Keys::new(dir.path().join("keys"));
+        for identity in ["../alice", "/etc/ssh/ssh_host_ed25519_key", "a/b", "..", ""] {
+            assert!(keys.sign(identity, b"entry").is_err(), "{identity:?}");
+            assert!(
+                keys.verify(identity, b"entry", "sig").is_err(),
+                "{identity:?}"
+            );
+        }
+    }
+}
@@ -2,64 +2,364 @@
 //!
 //! Each steer action maps to one or more `gh` CLI calls.
 //! Authentication uses the caller's identity via `GH_CONFIG_DIR`.
+//!
+//! Related actions share a submodule that knows how to perform them
+//! and what outcome to report back.
+
+mod checks;
+mod comment;
+mod conversation;
+mod edit;
+mod issue;
+mod pull_request;
+mod release;
+mod review;
+mod thread;
+mod triage;
 
-use std::{path::Path, process::Command};
+use std::{
+    fmt,
+    io::Write as _,
+    path::Path,
+    process::{Command, Stdio},
+};
 
-use crate::model::{CommentTarget, Steer};
+use crate::model::{Steer, SteerOutcome};
 
 /// Perform a steer action.
 ///
-/// Returns `Ok(())` on success, or an error message describing what failed.
-pub fn perform(steer: &Steer, gh_config: &Path) -> Result<(), String> {
+/// Returns what the action produced, if anything worth recording,
+/// or how it failed — before or after changing anything.
+pub fn perform(steer: &Steer, gh_config: &Path) -> Result<Option<SteerOutcome>, Failure> {
     match steer {
-        Steer::Comment {
-            number,
-            body,
-            target,
-        } => perform_comment(*number, body, target, gh_config),
-        _ => Err("this steer action is not yet implemented".to_string()),
+        Steer::Comment { .. } => comment::perform(steer, gh_config),
+        Steer::CreateIssue { .. } | Steer::CloseIssue { .. } | Steer::ReopenIssue { .. } => {
+            issue::perform(steer, gh_config)
+        }
+        Steer::EditIssue { .. } | Steer::EditPullRequest { .. } => edit::perform(steer, gh_config),
+        Steer::Label { .. } | Steer::Assign { .. } | Steer::SetMilestone { .. } => {
+            triage::perform(steer, gh_config)
+        }
+        Steer::CreatePullRequest { .. }
+        | Steer::ClosePullRequest { .. }
+        | Steer::ReopenPullRequest { .. }
+        | Steer::MarkReadyForReview { .. }
+        | Steer::ConvertToDraft { .. }
+        | Steer::UpdateBranch { .. }
+        | Steer::MergePullRequest { .. } => pull_request::perform(steer, gh_config),
+        Steer::RequestReview { .. }
+        | Steer::WithdrawReviewRequest { .. }
+        | Steer::SubmitReview { .. } => review::perform(steer, gh_config),
+        Steer::ResolveThread { .. } | Steer::UnresolveThread { .. } => {
+            thread::perform(steer, gh_config)
+        }
+        Steer::React { .. } | Steer::Unreact { .. } | Steer::Lock { .. } | Steer::Unlock { .. } => {
+            conversation::perform(steer, gh_config)
+        }
+        Steer::RerunChecks { .. } | Steer::DispatchWorkflow { .. } => {
+            checks::perform(steer, gh_config)
+        }
+        Steer::CreateRelease { .. } => release::perform(steer, gh_config),
     }
 }
 
-fn perform_comment(
-    number: u64,
-    body: &str,
-    target: &CommentTarget,
-    gh_config: &Path,
-) -> Result<(), String> {
-    let num = number.to_string();
-    match target {
-        CommentTarget::Issue => gh(&["issue", "comment", &num, "--body", body], gh_config),
-        CommentTarget::PullRequest => gh(&["pr", "comment", &num, "--body", body], gh_config),
-        CommentTarget::ReviewFeedback { comment_id } => {
-            let endpoint = format!("repos/{{owner}}/{{repo}}/pulls/comments/{comment_id}/replies");
-            gh(
-                &[
-                    "api",
-                    &endpoint,
-                    "--method",
-                    "POST",
-                    "-f",
-                    &format!("body={body}"),
-                ],
-                gh_config,
-            )
+/// How a steer failed: before anything changed, or after something had.
+///
+/// A plain error converts to [`Failure::NotPerformed`], so `?` suits every call
+/// up to a steer's first change. Anything failing after that must say what
+/// landed, with [`Failure::partial`] or [`Failure::unread`].
+#[derive(Debug)]
+pub enum Failure {
+    /// Nothing reached GitHub; the steer can be abandoned.
+    NotPerformed(String),
+
+    /// A change reached GitHub, then something failed: a later call, or
+    /// reading back what the change produced. The steer must still be recorded.
+    Partial {
+        /// What is known to have landed, if it produced anything.
+        landed: Option<SteerOutcome>,
+
+        /// What failed once the change had landed.
+        error: String,
+    },
+}
+
+impl Failure {
+    /// `error` happened after a change that produced `landed`.
+    fn partial(landed: Option<SteerOutcome>, error: String) -> Self {
+        Self::Partial { landed, error }
+    }
+
+    /// The change landed, but what it produced couldn't be read back.
+    fn unread(error: String) -> Self {
+        Self::partial(None, error)
+    }
+}
+
+impl From<String> for Failure {
+    fn from(error: String) -> Self {
+        Self::NotPerformed(error)
+    }
+}
+
+/// The `gh` invocations `perform` would run for a steer, in order, without running them.
+///
+/// Numbers that only exist once an earlier call has run (e.g. a new PR's)
+/// appear as placeholders.
+pub fn plan(steer: &Steer) -> Vec<Invocation> {
+    match steer {
+        Steer::Comment { .. } => comment::plan(steer),
+        Steer::CreateIssue { .. } | Steer::CloseIssue { .. } | Steer::ReopenIssue { .. } => {
+            issue::plan(steer)
+        }
+        Steer::EditIssue { .. } | Steer::EditPullRequest { .. } => edit::plan(steer),
+        Steer::Label { .. } | Steer::Assign { .. } | Steer::SetMilestone { .. } => {
+            triage::plan(steer)
+        }
+        Steer::CreatePullRequest { .. }
+        | Steer::ClosePullRequest { .. }
+        | Steer::ReopenPullRequest { .. }
+        | Steer::MarkReadyForReview { .. }
+        | Steer::ConvertToDraft { .. }
+        | Steer::UpdateBranch { .. }
+        | Steer::MergePullRequest { .. } => pull_request::plan(steer),
+        Steer::RequestReview { .. }
+        | Steer::WithdrawReviewRequest { .. }
+        | Steer::SubmitReview { .. } => review::plan(steer),
+        Steer::ResolveThread { .. } | Steer::UnresolveThread { .. } => thread::plan(steer),
+        Steer::React { .. } | Steer::Unreact { .. } | Steer::Lock { .. } | Steer::Unlock { .. } => {
+            conversation::plan(steer)
         }
+        Steer::RerunChecks { .. } | Steer::DispatchWorkflow { .. } => checks::plan(steer),
+        Steer::CreateRelease { .. } => release::plan(steer),
     }
 }
 
-/// Run a `gh` command with the given identity's config dir.
-fn gh(args: &[&str], gh_config: &Path) -> Result<(), String> {
-    let output = Command::new("gh")
-        .args(args)
-        .env("GH_CONFIG_DIR", gh_config)
-        .output()
-        .map_err(|e| format!("failed to run gh: {e}"))?;
+/// The repository `gh` acts on from here, as `owner/name`.
+///
+/// Steers don't name a repository: `gh` resolves it from the working
+/// directory, so asking `gh` gives the one a steer would land in.
+pub fn current_repo(gh_config: &Path) -> Result<String, String> {
+    let output = Invocation::new([
+        "repo",
+        "view",
+        "--json",
+        "nameWithOwner",
+        "--jq",
+        ".nameWithOwner",
+    ])
+    .run(gh_config)?;
+    Ok(output.trim().to_string())
+}
+
+/// One `gh` call a steer makes.
+///
+/// Performing and planning build invocations through the same functions,
+/// so a dry run shows exactly what a real run would execute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invocation {
+    /// Arguments after `gh`.
+    pub args: Vec<String>,
+
+    /// Request body piped to stdin, for `gh api --input -`.
+    pub input: Option<String>,
+}
+
+/// Stands in for a number that only exists once an earlier invocation has run.
+const CREATED_NUMBER: &str = "<created number>";
+
+impl Invocation {
+    fn new<S: Into<String>>(args: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            args: args.into_iter().map(Into::into).collect(),
+            input: None,
+        }
+    }
+
+    fn with_input(mut self, input: String) -> Self {
+        self.input = Some(input);
+        self
+    }
+
+    /// Run with the given identity's config dir.
+    ///
+    /// Returns stdout on success.
+    fn run(&self, gh_config: &Path) -> Result<String, String> {
+        let mut child = Command::new("gh")
+            .args(&self.args)
+            .env("GH_CONFIG_DIR", gh_config)
+            .stdin(if self.input.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("failed to run gh: {e}"))?;
 
-    if output.status.success() {
+        if let Some(input) = &self.input {
+            child
+                .stdin
+                .take()
+                .ok_or("failed to open gh stdin")?
+                .write_all(input.as_bytes())
+                .map_err(|e| format!("failed to write to gh stdin: {e}"))?;
+        }
+
+        let output = child
+            .wait_with_output()
+            .map_err(|e| format!("failed to run gh: {e}"))?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            Err(format!("gh command failed: {stderr}"))
+        }
+    }
+}
+
+/// Renders as a shell command line; stdin input becomes a heredoc.
+impl fmt::Display for Invocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("gh")?;
+        for arg in &self.args {
+            write!(f, " {}", shell_quote(arg))?;
+        }
+        if let Some(input) = &self.input {
+            write!(f, " <<'EOF'\n{input}\nEOF")?;
+        }
         Ok(())
+    }
+}
+
+/// Quote an argument for a POSIX shell, leaving plain words bare.
+fn shell_quote(arg: &str) -> String {
+    let plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@,%+".contains(c));
+    if plain {
+        arg.to_string()
     } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(format!("gh command failed: {stderr}"))
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+/// Parse the URL `gh` prints on creation into the new item's number and URL.
+///
+/// `gh issue create` and `gh pr create` print the new item's URL
+/// (e.g. `https://github.com/owner/repo/issues/46`) as their last line of output.
+fn parse_created_url(output: &str) -> Result<(u64, String), String> {
+    let url = output
+        .lines()
+        .map(str::trim)
+        .rfind(|l| !l.is_empty())
+        .ok_or("gh printed no URL")?;
+
+    let number = url
+        .rsplit('/')
+        .next()
+        .and_then(|n| n.parse::<u64>().ok())
+        .ok_or_else(|| format!("could not parse a number from URL '{url}'"))?;
+
+    Ok((number, url.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invocation_display_quotes_only_when_needed() {
+        let invocation = Invocation::new(["issue", "comment", "42", "--body", "It's done"]);
+        assert_eq!(
+            invocation.to_string(),
+            r"gh issue comment 42 --body 'It'\''s done'"
+        );
+    }
+
+    #[test]
+    fn invocation_display_renders_input_as_heredoc() {
+        let invocation = Invocation::new(["api", "x", "--input", "-"]).with_input("{}".into());
+        assert_eq!(
+            invocation.to_string(),
+            "gh api x --input - <<'EOF'\n{}\nEOF"
+        );
+    }
+
+    #[test]
+    fn plan_close_issue_posts_comment_first() {
+        let steer = Steer::CloseIssue {
+            number: 42,
+            reason: crate::model::CloseReason::NotPlanned,
+            comment: Some("Won't fix.".into()),
+        };
+        let invocations = plan(&steer);
+        assert_eq!(invocations.len(), 2);
+        assert_eq!(invocations[0].args[..3], ["issue", "comment", "42"]);
+        assert!(
+            invocations[1]
+                .args
+                .contains(&"state_reason=not_planned".to_string())
+        );
+    }
+
+    #[test]
+    fn plan_merge_pins_expected_head() {
+        let steer = Steer::MergePullRequest {
+            number: 45,
+            strategy: crate::model::MergeStrategy::Squash,
+            delete_branch: false,
+            expected_head: "abc123".into(),
+        };
+        let merge = &plan(&steer)[1];
+        assert_eq!(
+            merge.to_string(),
+            "gh pr merge 45 --squash --match-head-commit abc123"
+        );
+    }
+
+    #[test]
+    fn plan_draft_state_changes() {
+        let ready = plan(&Steer::MarkReadyForReview { number: 45 });
+        assert_eq!(ready[0].to_string(), "gh pr ready 45");
+        let draft = plan(&Steer::ConvertToDraft { number: 45 });
+        assert_eq!(draft[0].to_string(), "gh pr ready 45 --undo");
+    }
+
+    #[test]
+    fn plan_update_branch_reads_new_head() {
+        let invocations = plan(&Steer::UpdateBranch {
+            number: 45,
+            rebase: true,
+        });
+        assert_eq!(
+            invocations[0].to_string(),
+            "gh pr update-branch 45 --rebase"
+        );
+        assert_eq!(invocations[1].args[..3], ["pr", "view", "45"]);
+    }
+
+    #[test]
+    fn parse_issue_url() {
+        let (number, url) =
+            parse_created_url("https://github.com/dyreby/helm/issues/46\n").unwrap();
+        assert_eq!(number, 46);
+        assert_eq!(url, "https://github.com/dyreby/helm/issues/46");
+    }
+
+    #[test]
+    fn parse_created_url_uses_last_line() {
+        let output = "Creating issue in dyreby/helm\n\nhttps://github.com/dyreby/helm/pull/7\n";
+        let (number, _) = parse_created_url(output).unwrap();
+        assert_eq!(number, 7);
+    }
+
+    #[test]
+    fn parse_created_url_rejects_garbage() {
+        assert!(parse_created_url("").is_err());
+        assert!(parse_created_url("https://github.com/dyreby/helm/issues/").is_err());
     }
 }
@@ -0,0 +1,179 @@
+//! CI steers: re-run a PR's failed workflow runs, dispatch workflows.
+
+use std::{collections::BTreeMap, path::Path};
+
+use serde::Deserialize;
+
+use crate::model::{Steer, SteerOutcome};
+
+use super::{Failure, Invocation, pull_request};
+
+/// Stands in for the PR head until it has been read.
+const HEAD_SHA: &str = "<head sha>";
+
+/// Stands in for each failed run found on the head.
+const FAILED_RUN_ID: &str = "<failed run id>";
+
+/// Conclusions `gh run rerun --failed` can act on.
+const FAILED_CONCLUSIONS: &[&str] = &["failure", "timed_out", "cancelled", "startup_failure"];
+
+/// JSON shape for `gh run list --json databaseId,conclusion`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhRun {
+    database_id: u64,
+    conclusion: Option<String>,
+}
+
+/// Perform a CI steer: re-run failed checks, or dispatch a workflow.
+pub(super) fn perform(steer: &Steer, gh_config: &Path) -> Result<Option<SteerOutcome>, Failure> {
+    match steer {
+        Steer::RerunChecks { number } => rerun_failed(*number, gh_config).map(Some),
+        Steer::DispatchWorkflow {
+            workflow,
+            git_ref,
+            inputs,
+        } => dispatch(workflow, git_ref.as_deref(), inputs, gh_config).map(|()| None),
+        _ => unreachable!("not a CI steer: {}", steer.name()),
+    }
+}
+
+/// The `gh` calls [`perform`] makes, in order.
+pub(super) fn plan(steer: &Steer) -> Vec<Invocation> {
+    match steer {
+        Steer::RerunChecks { number } => plan_rerun(*number),
+        Steer::DispatchWorkflow {
+            workflow,
+            git_ref,
+            inputs,
+        } => vec![dispatch_invocation(workflow, git_ref.as_deref(), inputs)],
+        _ => unreachable!("not a CI steer: {}", steer.name()),
+    }
+}
+
+/// Re-run the failed jobs of every failed workflow run on a PR's current head.
+///
+/// Fails if nothing failed. Once one run has been re-run the world has
+/// changed, so a later failure ends the loop as a partial failure that
+/// reports what was re-run.
+fn rerun_failed(number: u64, gh_config: &Path) -> Result<SteerOutcome, Failure> {
+    let head_sha = pull_request::fetch_refs(number, gh_config)
+        .ok_or_else(|| format!("could not read the head of PR #{number}"))?
+        .head_ref_oid;
+
+    let json = runs_invocation(&head_sha).run(gh_config)?;
+    let runs: Vec<GhRun> =
+        serde_json::from_str(&json).map_err(|e| format!("failed to parse workflow runs: {e}"))?;
+    let failed: Vec<u64> = runs
+        .into_iter()
+        .filter(|r| {
+            r.conclusion
+                .as_deref()
+                .is_some_and(|c| FAILED_CONCLUSIONS.contains(&c))
+        })
+        .map(|r| r.database_id)
+        .collect();
+    if failed.is_empty() {
+        return Err(format!("no failed workflow runs on PR #{number} head {head_sha}").into());
+    }
+
+    let mut run_ids = Vec::new();
+    for id in failed {
+        if let Err(e) = rerun_invocation(&id.to_string()).run(gh_config) {
+            if run_ids.is_empty() {
+                return Err(e.into());
+            }
+            let rerun = SteerOutcome::ChecksRerun { head_sha, run_ids };
+            return Err(Failure::partial(
+                Some(rerun),
+                format!("failed to re-run workflow run {id}: {e}"),
+            ));
+        }
+        run_ids.push(id);
+    }
+    Ok(SteerOutcome::ChecksRerun { head_sha, run_ids })
+}
+
+/// The `gh` calls [`rerun_failed`] makes, in order; the last repeats per failed run.
+fn plan_rerun(number: u64) -> Vec<Invocation> {
+    vec![
+        pull_request::refs_invocation(&number.to_string()),
+        runs_invocation(HEAD_SHA),
+        rerun_invocation(FAILED_RUN_ID),
+    ]
+}
+
+fn runs_invocation(head_sha: &str) -> Invocation {
+    Invocation::new([
+        "run",
+        "list",
+        "--commit",
+        head_sha,
+        "--json",
+        "databaseId,conclusion",
+        "--limit",
+        "100",
+    ])
+}
+
+fn rerun_invocation(run_id: &str) -> Invocation {
+    Invocation::new(["run", "rerun", run_id, "--failed"])
+}
+
+/// Trigger a `workflow_dispatch` event. `gh` reports no run ID, so there is no outcome.
+fn dispatch(
+    workflow: &str,
+    git_ref: Option<&str>,
+    inputs: &BTreeMap<String, String>,
+    gh_config: &Path,
+) -> Result<(), Failure> {
+    dispatch_invocation(workflow, git_ref, inputs).run(gh_config)?;
+    Ok(())
+}
+
+fn dispatch_invocation(
+    workflow: &str,
+    git_ref: Option<&str>,
+    inputs: &BTreeMap<String, String>,
+) -> Invocation {
+    let mut args = vec!["workflow".to_string(), "run".into(), workflow.into()];
+    if let Some(git_ref) = git_ref {
+        args.extend(["--ref".into(), git_ref.into()]);
+    }
+    for (name, value) in inputs {
+        args.extend(["-f".into(), format!("{name}={value}")]);
+    }
+    Invocation::new(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan_rerun_reads_head_then_runs() {
+        let invocations = plan_rerun(45);
+        assert_eq!(invocations[0].args[..3], ["pr", "view", "45"]);
+        assert_eq!(
+            invocations[1].to_string(),
+            "gh run list --commit '<head sha>' --json databaseId,conclusion --limit 100"
+        );
+        assert_eq!(
+            invocations[2].to_string(),
+            "gh run rerun '<failed run id>' --failed"
+        );
+    }
+
+    #[test]
+    fn dispatch_passes_inputs_as_fields() {
+        let inputs = BTreeMap::from([
+            ("environment".to_string(), "staging".to_string()),
+            ("dry_run".to_string(), "true".to_string()),
+        ]);
+        let invocation = dispatch_invocation("deploy.yml", Some("main"), &inputs);
+        assert_eq!(
+            invocation.to_string(),
+            "gh workflow run deploy.yml --ref main -f dry_run=true -f environment=staging"
+        );
+    }
+}
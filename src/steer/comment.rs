@@ -0,0 +1,219 @@
+//! Comment steer: top-level comments and inline review replies.
+//!
+//! A reply names its review comment by ID alone, so it first reads the
+//! comment back and checks it's on the PR the steer names, as reactions do.
+
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::model::{CommentTarget, ReactionTarget, Steer, SteerOutcome, Subject};
+
+use super::{Failure, Invocation, conversation};
+
+/// JSON shape of a review comment reply from the REST API.
+#[derive(Deserialize)]
+struct GhReviewReply {
+    id: u64,
+    html_url: String,
+}
+
+/// Post a comment on an issue, PR, or inline review thread.
+///
+/// Reports the new comment's ID and URL.
+pub(super) fn perform(steer: &Steer, gh_config: &Path) -> Result<Option<SteerOutcome>, Failure> {
+    let (number, body, target) = comment(steer);
+    perform_with(number, body, target, |invocation| invocation.run(gh_config)).map(Some)
+}
+
+/// The `gh` calls [`perform`] makes, in order.
+pub(super) fn plan(steer: &Steer) -> Vec<Invocation> {
+    let (number, body, target) = comment(steer);
+    let mut invocations = Vec::new();
+    if let CommentTarget::ReviewFeedback { comment_id } = target {
+        invocations.push(conversation::comment_invocation(
+            ReactionTarget::ReviewComment {
+                comment_id: *comment_id,
+            },
+        ));
+    }
+    invocations.push(invocation(number, body, target));
+    invocations
+}
+
+/// The number, body, and target a comment steer names.
+fn comment(steer: &Steer) -> (u64, &str, &CommentTarget) {
+    let Steer::Comment {
+        number,
+        body,
+        target,
+    } = steer
+    else {
+        unreachable!("not a comment steer: {}", steer.name());
+    };
+    (*number, body, target)
+}
+
+/// [`perform`], running each call through `run`.
+fn perform_with(
+    number: u64,
+    body: &str,
+    target: &CommentTarget,
+    mut run: impl FnMut(&Invocation) -> Result<String, String>,
+) -> Result<SteerOutcome, Failure> {
+    if let CommentTarget::ReviewFeedback { comment_id } = target {
+        conversation::check_parent(
+            Subject::PullRequest,
+            number,
+            ReactionTarget::ReviewComment {
+                comment_id: *comment_id,
+            },
+            &mut run,
+        )?;
+    }
+    let output = run(&invocation(number, body, target))?;
+    match target {
+        CommentTarget::Issue | CommentTarget::PullRequest => {
+            let (id, url) = parse_comment_url(&output).map_err(Failure::unread)?;
+            Ok(SteerOutcome::CommentPosted { id, url })
+        }
+        CommentTarget::ReviewFeedback { .. } => {
+            let reply: GhReviewReply = serde_json::from_str(&output)
+                .map_err(|e| Failure::unread(format!("failed to parse review reply: {e}")))?;
+            Ok(SteerOutcome::CommentPosted {
+                id: reply.id,
+                url: reply.html_url,
+            })
+        }
+    }
+}
+
+/// The `gh` call that posts a comment on an issue, PR, or inline review thread.
+///
+/// Review replies go through the REST API; `gh pr comment` can't thread them.
+/// The reply path names the PR too, so GitHub also refuses a comment on another.
+fn invocation(number: u64, body: &str, target: &CommentTarget) -> Invocation {
+    match target {
+        CommentTarget::Issue => post_invocation("issue", number, body),
+        CommentTarget::PullRequest => post_invocation("pr", number, body),
+        CommentTarget::ReviewFeedback { comment_id } => Invocation::new([
+            "api",
+            &format!("repos/{{owner}}/{{repo}}/pulls/{number}/comments/{comment_id}/replies"),
+            "--method",
+            "POST",
+            "-f",
+            &format!("body={body}"),
+        ]),
+    }
+}
+
+/// Post a top-level comment on an issue (`subcommand = "issue"`) or PR (`"pr"`).
+///
+/// Shared with steers that leave a comment alongside a state change,
+/// which run each of their calls through `run`.
+pub(super) fn post(
+    subcommand: &str,
+    number: u64,
+    body: &str,
+    run: &mut impl FnMut(&Invocation) -> Result<String, String>,
+) -> Result<SteerOutcome, Failure> {
+    let output = run(&post_invocation(subcommand, number, body))?;
+    let (id, url) = parse_comment_url(&output).map_err(Failure::unread)?;
+    Ok(SteerOutcome::CommentPosted { id, url })
+}
+
+/// The `gh` call behind [`post`].
+pub(super) fn post_invocation(subcommand: &str, number: u64, body: &str) -> Invocation {
+    Invocation::new([subcommand, "comment", &number.to_string(), "--body", body])
+}
+
+/// Parse the URL `gh issue comment` / `gh pr comment` prints into the comment's ID and URL.
+///
+/// The URL ends in a fragment like `#issuecomment-1234567890`.
+fn parse_comment_url(output: &str) -> Result<(u64, String), String> {
+    let url = output
+        .lines()
+        .map(str::trim)
+        .rfind(|l| !l.is_empty())
+        .ok_or("gh printed no comment URL")?;
+
+    let id = url
+        .rsplit_once("#issuecomment-")
+        .and_then(|(_, id)| id.parse::<u64>().ok())
+        .ok_or_else(|| format!("could not parse a comment ID from URL '{url}'"))?;
+
+    Ok((id, url.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_issue_comment_url() {
+        let (id, url) =
+            parse_comment_url("https://github.com/dyreby/helm/issues/42#issuecomment-1234\n")
+                .unwrap();
+        assert_eq!(id, 1234);
+        assert_eq!(
+            url,
+            "https://github.com/dyreby/helm/issues/42#issuecomment-1234"
+        );
+    }
+
+    /// Answers as GitHub would for review comment 99, which is on PR #45.
+    fn gh(invocation: &Invocation) -> String {
+        if invocation.args[1] == "repos/{owner}/{repo}/pulls/comments/99" {
+            r#"{"id":99,"html_url":"https://github.com/dyreby/helm/pull/45#discussion_r99","pull_request_url":"https://api.github.com/repos/dyreby/helm/pulls/45"}"#.into()
+        } else {
+            r#"{"id":100,"html_url":"https://github.com/dyreby/helm/pull/45#discussion_r100"}"#
+                .into()
+        }
+    }
+
+    #[test]
+    fn reply_checks_the_comment_is_on_the_pr_first() {
+        let target = CommentTarget::ReviewFeedback { comment_id: 99 };
+        let invocations = plan(&Steer::Comment {
+            number: 45,
+            body: "Done.".into(),
+            target: target.clone(),
+        });
+        assert_eq!(
+            invocations[0].args,
+            ["api", "repos/{owner}/{repo}/pulls/comments/99"]
+        );
+        assert_eq!(
+            invocations[1].args[1],
+            "repos/{owner}/{repo}/pulls/45/comments/99/replies"
+        );
+
+        let outcome = perform_with(45, "Done.", &target, |i| Ok(gh(i))).unwrap();
+        assert!(matches!(
+            outcome,
+            SteerOutcome::CommentPosted { id: 100, .. }
+        ));
+    }
+
+    #[test]
+    fn reply_refuses_a_comment_on_another_pr() {
+        let target = CommentTarget::ReviewFeedback { comment_id: 99 };
+        let mut calls = Vec::new();
+        let failure = perform_with(99, "Done.", &target, |i| {
+            calls.push(i.clone());
+            Ok(gh(i))
+        })
+        .unwrap_err();
+        let Failure::NotPerformed(error) = failure else {
+            panic!("expected nothing to be performed");
+        };
+        assert_eq!(error, "review comment 99 is on PR #45, not PR #99");
+        assert_eq!(calls.len(), 1);
+    }
+
+    #[test]
+    fn parse_comment_url_rejects_missing_fragment() {
+        assert!(parse_comment_url("https://github.com/dyreby/helm/pull/45").is_err());
+        assert!(parse_comment_url("").is_err());
+    }
+}
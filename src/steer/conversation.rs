@@ -0,0 +1,473 @@
+//! Conversation steers: reactions on comments, and locking issue and PR conversations.
+//!
+//! Reactions go through the REST API. Removing one means finding it first:
+//! GitHub only deletes a reaction by its ID, and only the caller's own.
+//!
+//! GitHub knows a comment by its ID alone, so a reaction steer first reads
+//! the comment back and checks it's on the issue or PR the steer names —
+//! number and kind both — otherwise a policy scoped to that issue or PR
+//! could be stepped around.
+
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::model::{LockReason, Reaction, ReactionTarget, Steer, SteerOutcome, Subject};
+
+use super::{Failure, Invocation, triage::gh_subcommand};
+
+/// Stands in for the reaction's ID until it has been found.
+const REACTION_ID: &str = "<reaction id>";
+
+/// JSON shape for a reaction in the REST API.
+#[derive(Deserialize)]
+struct GhReaction {
+    id: u64,
+    content: String,
+    user: Option<GhUser>,
+}
+
+#[derive(Deserialize)]
+struct GhUser {
+    login: String,
+}
+
+/// The links from a comment to what it's on, in the REST API.
+///
+/// Conversation comments (on issues and PRs alike) carry `issue_url`, and
+/// only their `html_url` tells a PR's from an issue's; review comments
+/// carry `pull_request_url`.
+#[derive(Deserialize)]
+struct GhComment {
+    #[serde(rename = "html_url")]
+    page: String,
+    issue_url: Option<String>,
+    pull_request_url: Option<String>,
+}
+
+/// Perform a conversation steer: add or remove a reaction, or lock or unlock.
+pub(super) fn perform(steer: &Steer, gh_config: &Path) -> Result<Option<SteerOutcome>, Failure> {
+    match steer {
+        Steer::React {
+            number,
+            subject,
+            target,
+            reaction,
+        } => react(*subject, *number, *target, *reaction, gh_config).map(Some),
+        Steer::Unreact {
+            number,
+            subject,
+            target,
+            reaction,
+        } => unreact(*subject, *number, *target, *reaction, gh_config).map(Some),
+        Steer::Lock {
+            number,
+            subject,
+            reason,
+        } => lock(*subject, *number, *reason, gh_config).map(|()| None),
+        Steer::Unlock { number, subject } => unlock(*subject, *number, gh_config).map(|()| None),
+        _ => unreachable!("not a conversation steer: {}", steer.name()),
+    }
+}
+
+/// The `gh` calls [`perform`] makes, in order.
+pub(super) fn plan(steer: &Steer) -> Vec<Invocation> {
+    match steer {
+        Steer::React {
+            target, reaction, ..
+        } => plan_react(*target, *reaction),
+        Steer::Unreact { target, .. } => plan_unreact(*target),
+        Steer::Lock {
+            number,
+            subject,
+            reason,
+        } => vec![lock_invocation(*subject, *number, *reason)],
+        Steer::Unlock { number, subject } => vec![unlock_invocation(*subject, *number)],
+        _ => unreachable!("not a conversation steer: {}", steer.name()),
+    }
+}
+
+/// Add `reaction` to a comment on issue or PR `number`.
+/// Adding one that's already there is a no-op.
+fn react(
+    subject: Subject,
+    number: u64,
+    target: ReactionTarget,
+    reaction: Reaction,
+    gh_config: &Path,
+) -> Result<SteerOutcome, Failure> {
+    check_parent(subject, number, target, &mut |invocation| {
+        invocation.run(gh_config)
+    })?;
+    let json = react_invocation(target, reaction).run(gh_config)?;
+    let created: GhReaction = serde_json::from_str(&json)
+        .map_err(|e| Failure::unread(format!("failed to parse reaction response: {e}")))?;
+    Ok(SteerOutcome::Reacted {
+        reaction_id: created.id,
+    })
+}
+
+/// Remove the caller's own `reaction` from a comment on issue or PR `number`.
+fn unreact(
+    subject: Subject,
+    number: u64,
+    target: ReactionTarget,
+    reaction: Reaction,
+    gh_config: &Path,
+) -> Result<SteerOutcome, Failure> {
+    unreact_with(subject, number, target, reaction, |invocation| {
+        invocation.run(gh_config)
+    })
+}
+
+/// [`unreact`], running each call through `run`.
+fn unreact_with(
+    subject: Subject,
+    number: u64,
+    target: ReactionTarget,
+    reaction: Reaction,
+    mut run: impl FnMut(&Invocation) -> Result<String, String>,
+) -> Result<SteerOutcome, Failure> {
+    check_parent(subject, number, target, &mut run)?;
+    let login = run(&login_invocation())?;
+    let login = login.trim();
+    let json = run(&reactions_invocation(target))?;
+    let reaction_id = find_reaction(&json, login, reaction)?.ok_or_else(|| {
+        format!(
+            "{login} has no {} reaction on {}",
+            reaction.content(),
+            describe_target(target)
+        )
+    })?;
+
+    run(&unreact_invocation(target, &reaction_id.to_string()))?;
+    Ok(SteerOutcome::Unreacted { reaction_id })
+}
+
+/// The `gh` calls [`react`] makes, in order.
+fn plan_react(target: ReactionTarget, reaction: Reaction) -> Vec<Invocation> {
+    vec![
+        comment_invocation(target),
+        react_invocation(target, reaction),
+    ]
+}
+
+/// The `gh` calls [`unreact`] makes, in order.
+fn plan_unreact(target: ReactionTarget) -> Vec<Invocation> {
+    vec![
+        comment_invocation(target),
+        login_invocation(),
+        reactions_invocation(target),
+        unreact_invocation(target, REACTION_ID),
+    ]
+}
+
+/// Fail unless the comment `target` names is on the issue or PR `subject` and `number` name.
+pub(super) fn check_parent(
+    subject: Subject,
+    number: u64,
+    target: ReactionTarget,
+    run: &mut impl FnMut(&Invocation) -> Result<String, String>,
+) -> Result<(), String> {
+    let json = run(&comment_invocation(target))?;
+    let parent = parent(&json, target)?;
+    if parent == (subject, number) {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} is on {}, not {}",
+            describe_target(target),
+            describe_parent(parent),
+            describe_parent((subject, number))
+        ))
+    }
+}
+
+/// The issue or PR a comment is on, from its REST representation.
+///
+/// A review comment is always on a PR; a conversation comment is on
+/// whichever kind its page is.
+fn parent(json: &str, target: ReactionTarget) -> Result<(Subject, u64), String> {
+    let comment: GhComment =
+        serde_json::from_str(json).map_err(|e| format!("failed to parse comment: {e}"))?;
+    let (subject, url) = match target {
+        ReactionTarget::Comment { .. } => {
+            let page = comment.page.split('#').next().unwrap_or_default();
+            let subject = match page.rsplit('/').nth(1) {
+                Some("issues") => Subject::Issue,
+                Some("pull") => Subject::PullRequest,
+                _ => {
+                    return Err(format!(
+                        "could not tell an issue from a PR in URL '{}'",
+                        comment.page
+                    ));
+                }
+            };
+            (subject, comment.issue_url)
+        }
+        ReactionTarget::ReviewComment { .. } => (Subject::PullRequest, comment.pull_request_url),
+    };
+    let url = url.ok_or("comment names no issue or PR it's on")?;
+    let number = url
+        .rsplit('/')
+        .next()
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(|| format!("could not parse a number from URL '{url}'"))?;
+    Ok((subject, number))
+}
+
+fn describe_parent((subject, number): (Subject, u64)) -> String {
+    match subject {
+        Subject::Issue => format!("issue #{number}"),
+        Subject::PullRequest => format!("PR #{number}"),
+    }
+}
+
+pub(super) fn comment_invocation(target: ReactionTarget) -> Invocation {
+    let path = match target {
+        ReactionTarget::Comment { comment_id } => {
+            format!("repos/{{owner}}/{{repo}}/issues/comments/{comment_id}")
+        }
+        ReactionTarget::ReviewComment { comment_id } => {
+            format!("repos/{{owner}}/{{repo}}/pulls/comments/{comment_id}")
+        }
+    };
+    Invocation::new(["api".to_string(), path])
+}
+
+fn react_invocation(target: ReactionTarget, reaction: Reaction) -> Invocation {
+    Invocation::new([
+        "api".to_string(),
+        "--method".into(),
+        "POST".into(),
+        reactions_path(target),
+        "-f".into(),
+        format!("content={}", reaction.content()),
+    ])
+}
+
+fn login_invocation() -> Invocation {
+    Invocation::new(["api", "user", "--jq", ".login"])
+}
+
+fn reactions_invocation(target: ReactionTarget) -> Invocation {
+    Invocation::new([
+        "api".to_string(),
+        "--paginate".into(),
+        reactions_path(target),
+    ])
+}
+
+fn unreact_invocation(target: ReactionTarget, reaction_id: &str) -> Invocation {
+    Invocation::new([
+        "api".to_string(),
+        "--method".into(),
+        "DELETE".into(),
+        format!("{}/{reaction_id}", reactions_path(target)),
+    ])
+}
+
+fn reactions_path(target: ReactionTarget) -> String {
+    match target {
+        ReactionTarget::Comment { comment_id } => {
+            format!("repos/{{owner}}/{{repo}}/issues/comments/{comment_id}/reactions")
+        }
+        ReactionTarget::ReviewComment { comment_id } => {
+            format!("repos/{{owner}}/{{repo}}/pulls/comments/{comment_id}/reactions")
+        }
+    }
+}
+
+fn describe_target(target: ReactionTarget) -> String {
+    match target {
+        ReactionTarget::Comment { comment_id } => format!("comment {comment_id}"),
+        ReactionTarget::ReviewComment { comment_id } => format!("review comment {comment_id}"),
+    }
+}
+
+/// Find `login`'s `reaction` across all pages of a comment's reactions.
+fn find_reaction(json: &str, login: &str, reaction: Reaction) -> Result<Option<u64>, String> {
+    for page in serde_json::Deserializer::from_str(json).into_iter::<Vec<GhReaction>>() {
+        let page = page.map_err(|e| format!("failed to parse reactions: {e}"))?;
+        let found = page.into_iter().find(|r| {
+            r.content == reaction.content() && r.user.as_ref().is_some_and(|u| u.login == login)
+        });
+        if let Some(r) = found {
+            return Ok(Some(r.id));
+        }
+    }
+    Ok(None)
+}
+
+/// Lock the conversation on an issue or PR, with or without a reason.
+fn lock(
+    subject: Subject,
+    number: u64,
+    reason: Option<LockReason>,
+    gh_config: &Path,
+) -> Result<(), Failure> {
+    lock_invocation(subject, number, reason).run(gh_config)?;
+    Ok(())
+}
+
+/// Unlock the conversation on an issue or PR.
+fn unlock(subject: Subject, number: u64, gh_config: &Path) -> Result<(), Failure> {
+    unlock_invocation(subject, number).run(gh_config)?;
+    Ok(())
+}
+
+fn lock_invocation(subject: Subject, number: u64, reason: Option<LockReason>) -> Invocation {
+    let num = number.to_string();
+    let mut args = vec![gh_subcommand(subject), "lock", &num];
+    if let Some(reason) = reason {
+        args.extend([
+            "--reason",
+            match reason {
+                LockReason::OffTopic => "off_topic",
+                LockReason::Resolved => "resolved",
+                LockReason::Spam => "spam",
+                LockReason::TooHeated => "too_heated",
+            },
+        ]);
+    }
+    Invocation::new(args)
+}
+
+fn unlock_invocation(subject: Subject, number: u64) -> Invocation {
+    Invocation::new([gh_subcommand(subject), "unlock", &number.to_string()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn react_posts_content_to_the_comment() {
+        let invocation = react_invocation(
+            ReactionTarget::ReviewComment { comment_id: 99 },
+            Reaction::ThumbsUp,
+        );
+        assert_eq!(
+            invocation.args,
+            [
+                "api",
+                "--method",
+                "POST",
+                "repos/{owner}/{repo}/pulls/comments/99/reactions",
+                "-f",
+                "content=+1"
+            ]
+        );
+    }
+
+    #[test]
+    fn find_reaction_matches_login_and_content_across_pages() {
+        let json = r#"[{"id":1,"content":"+1","user":{"login":"someone"}},{"id":2,"content":"heart","user":{"login":"dyreby"}}]
+[{"id":3,"content":"+1","user":{"login":"dyreby"}},{"id":4,"content":"eyes","user":null}]"#;
+        assert_eq!(
+            find_reaction(json, "dyreby", Reaction::ThumbsUp).unwrap(),
+            Some(3)
+        );
+        assert_eq!(find_reaction(json, "dyreby", Reaction::Eyes).unwrap(), None);
+    }
+
+    #[test]
+    fn parent_is_read_from_the_comment() {
+        let comment = ReactionTarget::Comment { comment_id: 7 };
+        let on_issue = r#"{"id":7,"html_url":"https://github.com/dyreby/helm/issues/42#issuecomment-7","issue_url":"https://api.github.com/repos/dyreby/helm/issues/42"}"#;
+        assert_eq!(parent(on_issue, comment).unwrap(), (Subject::Issue, 42));
+        // A PR's conversation comments link to it through `issue_url` too.
+        let on_pr = r#"{"id":7,"html_url":"https://github.com/dyreby/helm/pull/45#issuecomment-7","issue_url":"https://api.github.com/repos/dyreby/helm/issues/45"}"#;
+        assert_eq!(parent(on_pr, comment).unwrap(), (Subject::PullRequest, 45));
+
+        let review = ReactionTarget::ReviewComment { comment_id: 99 };
+        let json = r#"{"id":99,"html_url":"https://github.com/dyreby/helm/pull/45#discussion_r99","pull_request_url":"https://api.github.com/repos/dyreby/helm/pulls/45"}"#;
+        assert_eq!(parent(json, review).unwrap(), (Subject::PullRequest, 45));
+        assert!(parent(on_issue, review).is_err());
+    }
+
+    /// Answers the unreact calls: the comment is on issue #42, and `dyreby` gave it a 👍.
+    fn gh(invocation: &Invocation) -> String {
+        let path = invocation.args.last().unwrap();
+        match path.as_str() {
+            ".login" => "dyreby\n".into(),
+            "repos/{owner}/{repo}/issues/comments/7" => {
+                r#"{"id":7,"html_url":"https://github.com/dyreby/helm/issues/42#issuecomment-7","issue_url":"https://api.github.com/repos/dyreby/helm/issues/42"}"#
+                    .into()
+            }
+            "repos/{owner}/{repo}/issues/comments/7/reactions" => {
+                r#"[{"id":3,"content":"+1","user":{"login":"dyreby"}}]"#.into()
+            }
+            _ => String::new(),
+        }
+    }
+
+    #[test]
+    fn unreact_reports_the_removed_reaction() {
+        let target = ReactionTarget::Comment { comment_id: 7 };
+        let outcome = unreact_with(Subject::Issue, 42, target, Reaction::ThumbsUp, |i| {
+            Ok(gh(i))
+        })
+        .unwrap();
+        assert!(matches!(
+            outcome,
+            SteerOutcome::Unreacted { reaction_id: 3 }
+        ));
+    }
+
+    #[test]
+    fn unreact_refuses_a_comment_on_another_number() {
+        let target = ReactionTarget::Comment { comment_id: 7 };
+        let failure = unreact_with(Subject::Issue, 45, target, Reaction::ThumbsUp, |i| {
+            Ok(gh(i))
+        })
+        .unwrap_err();
+        let Failure::NotPerformed(error) = failure else {
+            panic!("expected nothing to be performed");
+        };
+        assert_eq!(error, "comment 7 is on issue #42, not issue #45");
+    }
+
+    #[test]
+    fn unreact_refuses_a_comment_on_the_other_kind() {
+        let target = ReactionTarget::Comment { comment_id: 7 };
+        let failure = unreact_with(Subject::PullRequest, 42, target, Reaction::ThumbsUp, |i| {
+            Ok(gh(i))
+        })
+        .unwrap_err();
+        let Failure::NotPerformed(error) = failure else {
+            panic!("expected nothing to be performed");
+        };
+        assert_eq!(error, "comment 7 is on issue #42, not PR #42");
+    }
+
+    #[test]
+    fn plans_check_the_comment_first() {
+        let target = ReactionTarget::ReviewComment { comment_id: 99 };
+        let react = plan_react(target, Reaction::Eyes);
+        assert_eq!(
+            react[0].args,
+            ["api", "repos/{owner}/{repo}/pulls/comments/99"]
+        );
+        assert_eq!(plan_unreact(target)[0], react[0]);
+    }
+
+    #[test]
+    fn plan_unreact_deletes_found_reaction() {
+        let invocations = plan_unreact(ReactionTarget::Comment { comment_id: 7 });
+        assert_eq!(invocations[1].args, ["api", "user", "--jq", ".login"]);
+        assert_eq!(
+            invocations[3].args.last().unwrap(),
+            &format!("repos/{{owner}}/{{repo}}/issues/comments/7/reactions/{REACTION_ID}")
+        );
+    }
+
+    #[test]
+    fn lock_passes_gh_reason() {
+        let invocation = lock_invocation(Subject::PullRequest, 45, Some(LockReason::TooHeated));
+        assert_eq!(
+            invocation.args,
+            ["pr", "lock", "45", "--reason", "too_heated"]
+        );
+    }
+}
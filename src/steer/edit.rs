@@ -0,0 +1,142 @@
+//! Edit steers: change an issue's or PR's title, body, or labels.
+//!
+//! Issues and PRs share one flow — `gh issue edit` and `gh pr edit` take the same
+//! flags — so both go through [`edit`] with the `gh` subcommand as the only difference.
+
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::model::{EditSnapshot, Steer, SteerOutcome};
+
+use super::{Failure, Invocation};
+
+/// The changes an edit applies. Empty fields leave that part untouched.
+struct Changes<'a> {
+    title: Option<&'a str>,
+    body: Option<&'a str>,
+    add_labels: &'a [String],
+    remove_labels: &'a [String],
+}
+
+/// JSON shape for `gh issue view --json title,body,labels` (and `gh pr view`).
+#[derive(Deserialize)]
+struct GhEditable {
+    title: String,
+    body: String,
+    labels: Vec<GhLabel>,
+}
+
+#[derive(Deserialize)]
+struct GhLabel {
+    name: String,
+}
+
+/// Perform an edit steer, on an issue or a PR.
+pub(super) fn perform(steer: &Steer, gh_config: &Path) -> Result<Option<SteerOutcome>, Failure> {
+    let (subcommand, number, changes) = changes(steer);
+    edit(subcommand, number, &changes, gh_config).map(Some)
+}
+
+/// The `gh` calls [`perform`] makes, in order.
+pub(super) fn plan(steer: &Steer) -> Vec<Invocation> {
+    let (subcommand, number, changes) = changes(steer);
+    plan_edit(subcommand, number, &changes)
+}
+
+/// The `gh` subcommand, number, and changes an edit steer names.
+fn changes(steer: &Steer) -> (&'static str, u64, Changes<'_>) {
+    let (subcommand, number, title, body, add_labels, remove_labels) = match steer {
+        Steer::EditIssue {
+            number,
+            title,
+            body,
+            add_labels,
+            remove_labels,
+        } => ("issue", number, title, body, add_labels, remove_labels),
+        Steer::EditPullRequest {
+            number,
+            title,
+            body,
+            add_labels,
+            remove_labels,
+        } => ("pr", number, title, body, add_labels, remove_labels),
+        _ => unreachable!("not an edit steer: {}", steer.name()),
+    };
+    let changes = Changes {
+        title: title.as_deref(),
+        body: body.as_deref(),
+        add_labels,
+        remove_labels,
+    };
+    (subcommand, *number, changes)
+}
+
+/// Edit an issue (`subcommand = "issue"`) or PR (`subcommand = "pr"`).
+///
+/// Reads the current title, body, and labels first so the outcome
+/// records what was replaced, then reads them again after the edit.
+fn edit(
+    subcommand: &str,
+    number: u64,
+    changes: &Changes<'_>,
+    gh_config: &Path,
+) -> Result<SteerOutcome, Failure> {
+    let before = Box::new(fetch_snapshot(subcommand, number, gh_config)?);
+
+    edit_invocation(subcommand, number, changes).run(gh_config)?;
+
+    // The edit has landed — failing to read it back must not lose the record.
+    let after = fetch_snapshot(subcommand, number, gh_config)
+        .ok()
+        .map(Box::new);
+    Ok(SteerOutcome::Edited { before, after })
+}
+
+/// The `gh` calls [`edit`] makes, in order.
+fn plan_edit(subcommand: &str, number: u64, changes: &Changes<'_>) -> Vec<Invocation> {
+    vec![
+        snapshot_invocation(subcommand, number),
+        edit_invocation(subcommand, number, changes),
+        snapshot_invocation(subcommand, number),
+    ]
+}
+
+fn edit_invocation(subcommand: &str, number: u64, changes: &Changes<'_>) -> Invocation {
+    let num = number.to_string();
+    let mut args = vec![subcommand, "edit", &num];
+    if let Some(title) = changes.title {
+        args.extend(["--title", title]);
+    }
+    if let Some(body) = changes.body {
+        args.extend(["--body", body]);
+    }
+    for label in changes.add_labels {
+        args.extend(["--add-label", label]);
+    }
+    for label in changes.remove_labels {
+        args.extend(["--remove-label", label]);
+    }
+    Invocation::new(args)
+}
+
+fn fetch_snapshot(subcommand: &str, number: u64, gh_config: &Path) -> Result<EditSnapshot, String> {
+    let json = snapshot_invocation(subcommand, number).run(gh_config)?;
+    let view: GhEditable = serde_json::from_str(&json)
+        .map_err(|e| format!("failed to parse gh {subcommand} view output: {e}"))?;
+    Ok(EditSnapshot {
+        title: view.title,
+        body: view.body,
+        labels: view.labels.into_iter().map(|l| l.name).collect(),
+    })
+}
+
+fn snapshot_invocation(subcommand: &str, number: u64) -> Invocation {
+    Invocation::new([
+        subcommand,
+        "view",
+        &number.to_string(),
+        "--json",
+        "title,body,labels",
+    ])
+}
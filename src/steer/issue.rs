@@ -0,0 +1,256 @@
+//! Issue steers: create, close, and reopen issues.
+
+use std::path::Path;
+
+use crate::model::{CloseReason, Steer, SteerOutcome};
+
+use super::{Failure, Invocation, comment, parse_created_url};
+
+/// Perform an issue steer: create, close, or reopen.
+pub(super) fn perform(steer: &Steer, gh_config: &Path) -> Result<Option<SteerOutcome>, Failure> {
+    match steer {
+        Steer::CreateIssue {
+            title,
+            body,
+            labels,
+            assignees,
+            milestone,
+        } => create(
+            title,
+            body,
+            labels,
+            assignees,
+            milestone.as_deref(),
+            gh_config,
+        )
+        .map(Some),
+        Steer::CloseIssue {
+            number,
+            reason,
+            comment,
+        } => close(*number, *reason, comment.as_deref(), gh_config),
+        Steer::ReopenIssue { number, comment } => reopen(*number, comment.as_deref(), gh_config),
+        _ => unreachable!("not an issue steer: {}", steer.name()),
+    }
+}
+
+/// The `gh` calls [`perform`] makes, in order.
+pub(super) fn plan(steer: &Steer) -> Vec<Invocation> {
+    match steer {
+        Steer::CreateIssue {
+            title,
+            body,
+            labels,
+            assignees,
+            milestone,
+        } => vec![create_invocation(
+            title,
+            body,
+            labels,
+            assignees,
+            milestone.as_deref(),
+        )],
+        Steer::CloseIssue {
+            number,
+            reason,
+            comment,
+        } => plan_close(*number, *reason, comment.as_deref()),
+        Steer::ReopenIssue { number, comment } => plan_reopen(*number, comment.as_deref()),
+        _ => unreachable!("not an issue steer: {}", steer.name()),
+    }
+}
+
+/// Create an issue and report its number and URL.
+fn create(
+    title: &str,
+    body: &str,
+    labels: &[String],
+    assignees: &[String],
+    milestone: Option<&str>,
+    gh_config: &Path,
+) -> Result<SteerOutcome, Failure> {
+    let output = create_invocation(title, body, labels, assignees, milestone).run(gh_config)?;
+    let (number, url) = parse_created_url(&output).map_err(Failure::unread)?;
+    Ok(SteerOutcome::IssueCreated { number, url })
+}
+
+/// The `gh` call behind [`create`].
+fn create_invocation(
+    title: &str,
+    body: &str,
+    labels: &[String],
+    assignees: &[String],
+    milestone: Option<&str>,
+) -> Invocation {
+    let mut args = vec!["issue", "create", "--title", title, "--body", body];
+    for label in labels {
+        args.extend(["--label", label]);
+    }
+    for assignee in assignees {
+        args.extend(["--assignee", assignee]);
+    }
+    if let Some(milestone) = milestone {
+        args.extend(["--milestone", milestone]);
+    }
+    Invocation::new(args)
+}
+
+/// Close an issue with a reason, posting the closing comment first if given.
+///
+/// `gh issue close` can't express every `state_reason`, so the state change
+/// goes through the REST API. Reports the closing comment, if one was posted.
+/// A comment posted ahead of a failed close is reported as a partial failure.
+fn close(
+    number: u64,
+    reason: CloseReason,
+    comment: Option<&str>,
+    gh_config: &Path,
+) -> Result<Option<SteerOutcome>, Failure> {
+    close_with(number, reason, comment, |invocation| {
+        invocation.run(gh_config)
+    })
+}
+
+/// [`close`], running each call through `run`.
+fn close_with(
+    number: u64,
+    reason: CloseReason,
+    comment: Option<&str>,
+    mut run: impl FnMut(&Invocation) -> Result<String, String>,
+) -> Result<Option<SteerOutcome>, Failure> {
+    let posted = comment
+        .map(|body| comment::post("issue", number, body, &mut run))
+        .transpose()?;
+    if let Err(e) = run(&close_invocation(number, reason)) {
+        return Err(match posted {
+            None => e.into(),
+            Some(posted) => Failure::partial(
+                Some(posted),
+                format!("posted the closing comment, but failed to close issue #{number}: {e}"),
+            ),
+        });
+    }
+    Ok(posted)
+}
+
+/// The `gh` calls [`close`] makes, in order.
+fn plan_close(number: u64, reason: CloseReason, comment: Option<&str>) -> Vec<Invocation> {
+    comment
+        .map(|body| comment::post_invocation("issue", number, body))
+        .into_iter()
+        .chain([close_invocation(number, reason)])
+        .collect()
+}
+
+fn close_invocation(number: u64, reason: CloseReason) -> Invocation {
+    let state_reason = match reason {
+        CloseReason::Completed => "completed",
+        CloseReason::NotPlanned => "not_planned",
+        CloseReason::Duplicate => "duplicate",
+    };
+    Invocation::new([
+        "api",
+        &format!("repos/{{owner}}/{{repo}}/issues/{number}"),
+        "--method",
+        "PATCH",
+        "-f",
+        "state=closed",
+        "-f",
+        &format!("state_reason={state_reason}"),
+    ])
+}
+
+/// Reopen a closed issue, then post the comment if given.
+///
+/// Reports the comment, if one was posted. A comment failing after the
+/// reopen is reported as a partial failure.
+fn reopen(
+    number: u64,
+    comment: Option<&str>,
+    gh_config: &Path,
+) -> Result<Option<SteerOutcome>, Failure> {
+    reopen_with(number, comment, |invocation| invocation.run(gh_config))
+}
+
+/// [`reopen`], running each call through `run`.
+fn reopen_with(
+    number: u64,
+    comment: Option<&str>,
+    mut run: impl FnMut(&Invocation) -> Result<String, String>,
+) -> Result<Option<SteerOutcome>, Failure> {
+    run(&reopen_invocation(number))?;
+    comment
+        .map(|body| comment::post("issue", number, body, &mut run))
+        .transpose()
+        .map_err(|failure| match failure {
+            Failure::NotPerformed(e) => Failure::partial(
+                None,
+                format!("reopened issue #{number}, but failed to post the comment: {e}"),
+            ),
+            partial @ Failure::Partial { .. } => partial,
+        })
+}
+
+/// The `gh` calls [`reopen`] makes, in order.
+fn plan_reopen(number: u64, comment: Option<&str>) -> Vec<Invocation> {
+    [reopen_invocation(number)]
+        .into_iter()
+        .chain(comment.map(|body| comment::post_invocation("issue", number, body)))
+        .collect()
+}
+
+fn reopen_invocation(number: u64) -> Invocation {
+    Invocation::new(["issue", "reopen", &number.to_string()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Answers comment calls with a comment URL and fails the call `failing` names.
+    fn gh(failing: &'static str) -> impl FnMut(&Invocation) -> Result<String, String> {
+        move |invocation| {
+            if invocation.args.iter().any(|arg| arg.contains(failing)) {
+                Err("gh command failed: HTTP 502".into())
+            } else if invocation.args[1] == "comment" {
+                Ok("https://github.com/dyreby/helm/issues/42#issuecomment-7\n".into())
+            } else {
+                Ok(String::new())
+            }
+        }
+    }
+
+    #[test]
+    fn close_failing_after_the_comment_reports_the_comment() {
+        let failure = close_with(
+            42,
+            CloseReason::Completed,
+            Some("Done."),
+            gh("state=closed"),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            failure,
+            Failure::Partial {
+                landed: Some(SteerOutcome::CommentPosted { id: 7, .. }),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn close_failing_without_a_comment_performed_nothing() {
+        let failure = close_with(42, CloseReason::Completed, None, gh("state=closed")).unwrap_err();
+        assert!(matches!(failure, Failure::NotPerformed(_)));
+    }
+
+    #[test]
+    fn comment_failing_after_the_reopen_is_partial() {
+        let failure = reopen_with(42, Some("Back again."), gh("comment")).unwrap_err();
+        let Failure::Partial { landed, error } = failure else {
+            panic!("expected a partial failure");
+        };
+        assert!(landed.is_none());
+        assert!(error.starts_with("reopened issue #42, but failed to post the comment"));
+    }
+}
@@ -0,0 +1,408 @@
+//! Pull request steers: create, merge, close, reopen, draft state, and branch updates.
+
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::model::{MergeStrategy, Steer, SteerOutcome};
+
+use super::{CREATED_NUMBER, Failure, Invocation, comment, parse_created_url};
+
+/// JSON shape for `gh pr view --json headRefOid,baseRefOid`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct GhPrRefs {
+    pub head_ref_oid: String,
+    base_ref_oid: String,
+}
+
+/// Perform a pull request steer.
+pub(super) fn perform(steer: &Steer, gh_config: &Path) -> Result<Option<SteerOutcome>, Failure> {
+    match steer {
+        Steer::CreatePullRequest {
+            head,
+            base,
+            title,
+            body,
+            draft,
+            reviewers,
+        } => create(head, base, title, body, *draft, reviewers, gh_config).map(Some),
+        Steer::ClosePullRequest { number, comment } => {
+            close(*number, comment.as_deref(), gh_config)
+        }
+        Steer::ReopenPullRequest { number, comment } => {
+            reopen(*number, comment.as_deref(), gh_config)
+        }
+        Steer::MarkReadyForReview { number } => set_draft(*number, false, gh_config).map(|()| None),
+        Steer::ConvertToDraft { number } => set_draft(*number, true, gh_config).map(|()| None),
+        Steer::UpdateBranch { number, rebase } => {
+            update_branch(*number, *rebase, gh_config).map(Some)
+        }
+        Steer::MergePullRequest {
+            number,
+            strategy,
+            delete_branch,
+            expected_head,
+        } => merge(*number, *strategy, *delete_branch, expected_head, gh_config).map(Some),
+        _ => unreachable!("not a pull request steer: {}", steer.name()),
+    }
+}
+
+/// The `gh` calls [`perform`] makes, in order.
+pub(super) fn plan(steer: &Steer) -> Vec<Invocation> {
+    match steer {
+        Steer::CreatePullRequest {
+            head,
+            base,
+            title,
+            body,
+            draft,
+            reviewers,
+        } => plan_create(head, base, title, body, *draft, reviewers),
+        Steer::ClosePullRequest { number, comment } => plan_close(*number, comment.as_deref()),
+        Steer::ReopenPullRequest { number, comment } => plan_reopen(*number, comment.as_deref()),
+        Steer::MarkReadyForReview { number } => vec![draft_invocation(*number, false)],
+        Steer::ConvertToDraft { number } => vec![draft_invocation(*number, true)],
+        Steer::UpdateBranch { number, rebase } => plan_update_branch(*number, *rebase),
+        Steer::MergePullRequest {
+            number,
+            strategy,
+            delete_branch,
+            expected_head,
+        } => plan_merge(*number, *strategy, *delete_branch, expected_head),
+        _ => unreachable!("not a pull request steer: {}", steer.name()),
+    }
+}
+
+/// Create a pull request and report its number, URL, and head/base commits.
+fn create(
+    head: &str,
+    base: &str,
+    title: &str,
+    body: &str,
+    draft: bool,
+    reviewers: &[String],
+    gh_config: &Path,
+) -> Result<SteerOutcome, Failure> {
+    let output = create_invocation(head, base, title, body, draft, reviewers).run(gh_config)?;
+    let (number, url) = parse_created_url(&output).map_err(Failure::unread)?;
+
+    // The PR exists now — failing to read its refs back must not lose the record.
+    let refs = fetch_refs(number, gh_config);
+    Ok(SteerOutcome::PullRequestCreated {
+        number,
+        url,
+        head_sha: refs.as_ref().map(|r| r.head_ref_oid.clone()),
+        base_sha: refs.map(|r| r.base_ref_oid),
+    })
+}
+
+/// The `gh` calls [`create`] makes, in order.
+fn plan_create(
+    head: &str,
+    base: &str,
+    title: &str,
+    body: &str,
+    draft: bool,
+    reviewers: &[String],
+) -> Vec<Invocation> {
+    vec![
+        create_invocation(head, base, title, body, draft, reviewers),
+        refs_invocation(CREATED_NUMBER),
+    ]
+}
+
+fn create_invocation(
+    head: &str,
+    base: &str,
+    title: &str,
+    body: &str,
+    draft: bool,
+    reviewers: &[String],
+) -> Invocation {
+    let mut args = vec![
+        "pr", "create", "--head", head, "--base", base, "--title", title, "--body", body,
+    ];
+    if draft {
+        args.push("--draft");
+    }
+    for reviewer in reviewers {
+        args.extend(["--reviewer", reviewer]);
+    }
+    Invocation::new(args)
+}
+
+/// JSON shape for `gh pr view --json mergeCommit`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhPrMergeCommit {
+    merge_commit: Option<GhCommit>,
+}
+
+#[derive(Deserialize)]
+struct GhCommit {
+    oid: String,
+}
+
+/// Merge a pull request, refusing if its head has moved past `expected_head`.
+///
+/// The guard is checked twice: once here before calling `gh`, so the error
+/// names both commits, and again by GitHub via `--match-head-commit`,
+/// which closes the window between the check and the merge.
+fn merge(
+    number: u64,
+    strategy: MergeStrategy,
+    delete_branch: bool,
+    expected_head: &str,
+    gh_config: &Path,
+) -> Result<SteerOutcome, Failure> {
+    let refs = fetch_refs(number, gh_config)
+        .ok_or_else(|| format!("could not read the head of PR #{number}"))?;
+    if refs.head_ref_oid != expected_head {
+        return Err(format!(
+            "refusing to merge PR #{number}: head moved from {expected_head} to {} \
+             since it was observed",
+            refs.head_ref_oid
+        )
+        .into());
+    }
+
+    merge_invocation(number, strategy, delete_branch, expected_head).run(gh_config)?;
+
+    // The PR is merged now — failing to read the merge commit must not lose the record.
+    let merge_commit_sha = merge_commit_invocation(number)
+        .run(gh_config)
+        .ok()
+        .and_then(|json| serde_json::from_str::<GhPrMergeCommit>(&json).ok())
+        .and_then(|m| m.merge_commit)
+        .map(|c| c.oid);
+
+    Ok(SteerOutcome::PullRequestMerged { merge_commit_sha })
+}
+
+/// The `gh` calls [`merge`] makes, in order.
+fn plan_merge(
+    number: u64,
+    strategy: MergeStrategy,
+    delete_branch: bool,
+    expected_head: &str,
+) -> Vec<Invocation> {
+    vec![
+        refs_invocation(&number.to_string()),
+        merge_invocation(number, strategy, delete_branch, expected_head),
+        merge_commit_invocation(number),
+    ]
+}
+
+fn merge_invocation(
+    number: u64,
+    strategy: MergeStrategy,
+    delete_branch: bool,
+    expected_head: &str,
+) -> Invocation {
+    let num = number.to_string();
+    let strategy_flag = match strategy {
+        MergeStrategy::Merge => "--merge",
+        MergeStrategy::Squash => "--squash",
+        MergeStrategy::Rebase => "--rebase",
+    };
+    let mut args = vec![
+        "pr",
+        "merge",
+        &num,
+        strategy_flag,
+        "--match-head-commit",
+        expected_head,
+    ];
+    if delete_branch {
+        args.push("--delete-branch");
+    }
+    Invocation::new(args)
+}
+
+fn merge_commit_invocation(number: u64) -> Invocation {
+    Invocation::new(["pr", "view", &number.to_string(), "--json", "mergeCommit"])
+}
+
+/// Close a PR without merging, posting the closing comment first if given.
+///
+/// Reports the closing comment, if one was posted.
+/// A comment posted ahead of a failed close is reported as a partial failure.
+fn close(
+    number: u64,
+    comment: Option<&str>,
+    gh_config: &Path,
+) -> Result<Option<SteerOutcome>, Failure> {
+    close_with(number, comment, |invocation| invocation.run(gh_config))
+}
+
+/// [`close`], running each call through `run`.
+fn close_with(
+    number: u64,
+    comment: Option<&str>,
+    mut run: impl FnMut(&Invocation) -> Result<String, String>,
+) -> Result<Option<SteerOutcome>, Failure> {
+    let posted = comment
+        .map(|body| comment::post("pr", number, body, &mut run))
+        .transpose()?;
+    if let Err(e) = run(&close_invocation(number)) {
+        return Err(match posted {
+            None => e.into(),
+            Some(posted) => Failure::partial(
+                Some(posted),
+                format!("posted the closing comment, but failed to close PR #{number}: {e}"),
+            ),
+        });
+    }
+    Ok(posted)
+}
+
+/// The `gh` calls [`close`] makes, in order.
+fn plan_close(number: u64, comment: Option<&str>) -> Vec<Invocation> {
+    comment
+        .map(|body| comment::post_invocation("pr", number, body))
+        .into_iter()
+        .chain([close_invocation(number)])
+        .collect()
+}
+
+fn close_invocation(number: u64) -> Invocation {
+    Invocation::new(["pr", "close", &number.to_string()])
+}
+
+/// Reopen a closed PR, then post the comment if given.
+///
+/// Reports the comment, if one was posted. A comment failing after the
+/// reopen is reported as a partial failure.
+fn reopen(
+    number: u64,
+    comment: Option<&str>,
+    gh_config: &Path,
+) -> Result<Option<SteerOutcome>, Failure> {
+    reopen_with(number, comment, |invocation| invocation.run(gh_config))
+}
+
+/// [`reopen`], running each call through `run`.
+fn reopen_with(
+    number: u64,
+    comment: Option<&str>,
+    mut run: impl FnMut(&Invocation) -> Result<String, String>,
+) -> Result<Option<SteerOutcome>, Failure> {
+    run(&reopen_invocation(number))?;
+    comment
+        .map(|body| comment::post("pr", number, body, &mut run))
+        .transpose()
+        .map_err(|failure| match failure {
+            Failure::NotPerformed(e) => Failure::partial(
+                None,
+                format!("reopened PR #{number}, but failed to post the comment: {e}"),
+            ),
+            partial @ Failure::Partial { .. } => partial,
+        })
+}
+
+/// The `gh` calls [`reopen`] makes, in order.
+fn plan_reopen(number: u64, comment: Option<&str>) -> Vec<Invocation> {
+    [reopen_invocation(number)]
+        .into_iter()
+        .chain(comment.map(|body| comment::post_invocation("pr", number, body)))
+        .collect()
+}
+
+fn reopen_invocation(number: u64) -> Invocation {
+    Invocation::new(["pr", "reopen", &number.to_string()])
+}
+
+/// Mark a draft PR ready for review (`draft = false`), or convert it to a draft (`draft = true`).
+fn set_draft(number: u64, draft: bool, gh_config: &Path) -> Result<(), Failure> {
+    draft_invocation(number, draft).run(gh_config)?;
+    Ok(())
+}
+
+fn draft_invocation(number: u64, draft: bool) -> Invocation {
+    let num = number.to_string();
+    let mut args = vec!["pr", "ready", &num];
+    if draft {
+        args.push("--undo");
+    }
+    Invocation::new(args)
+}
+
+/// Update a PR's head branch from its base and report the new head commit.
+fn update_branch(number: u64, rebase: bool, gh_config: &Path) -> Result<SteerOutcome, Failure> {
+    update_branch_invocation(number, rebase).run(gh_config)?;
+
+    // The branch has moved — failing to read the new head must not lose the record.
+    let head_sha = fetch_refs(number, gh_config).map(|r| r.head_ref_oid);
+    Ok(SteerOutcome::BranchUpdated { head_sha })
+}
+
+/// The `gh` calls [`update_branch`] makes, in order.
+fn plan_update_branch(number: u64, rebase: bool) -> Vec<Invocation> {
+    vec![
+        update_branch_invocation(number, rebase),
+        refs_invocation(&number.to_string()),
+    ]
+}
+
+fn update_branch_invocation(number: u64, rebase: bool) -> Invocation {
+    let num = number.to_string();
+    let mut args = vec!["pr", "update-branch", &num];
+    if rebase {
+        args.push("--rebase");
+    }
+    Invocation::new(args)
+}
+
+/// Read a PR's current head and base commits, or `None` on failure.
+pub(super) fn fetch_refs(number: u64, gh_config: &Path) -> Option<GhPrRefs> {
+    let json = refs_invocation(&number.to_string()).run(gh_config).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// `number` is a string so plans can refer to a PR that doesn't exist yet.
+pub(super) fn refs_invocation(number: &str) -> Invocation {
+    Invocation::new(["pr", "view", number, "--json", "headRefOid,baseRefOid"])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Answers comment calls with a comment URL and fails the `gh pr` subcommand `failing`.
+    fn gh(failing: &'static str) -> impl FnMut(&Invocation) -> Result<String, String> {
+        move |invocation| match invocation.args[1].as_str() {
+            subcommand if subcommand == failing => Err("gh command failed: HTTP 502".into()),
+            "comment" => Ok("https://github.com/dyreby/helm/pull/45#issuecomment-7\n".into()),
+            _ => Ok(String::new()),
+        }
+    }
+
+    #[test]
+    fn close_failing_after_the_comment_reports_the_comment() {
+        let failure = close_with(45, Some("Superseded."), gh("close")).unwrap_err();
+        assert!(matches!(
+            failure,
+            Failure::Partial {
+                landed: Some(SteerOutcome::CommentPosted { id: 7, .. }),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn comment_failing_after_the_reopen_is_partial() {
+        let failure = reopen_with(45, Some("Back again."), gh("comment")).unwrap_err();
+        let Failure::Partial { landed, error } = failure else {
+            panic!("expected a partial failure");
+        };
+        assert!(landed.is_none());
+        assert!(error.starts_with("reopened PR #45, but failed to post the comment"));
+    }
+
+    #[test]
+    fn reopen_failing_performed_nothing() {
+        let failure = reopen_with(45, Some("Back again."), gh("reopen")).unwrap_err();
+        assert!(matches!(failure, Failure::NotPerformed(_)));
+    }
+}
@@ -0,0 +1,113 @@
+//! Release steers: create a release and its tag.
+
+use std::path::Path;
+
+use crate::model::{Steer, SteerOutcome};
+
+use super::{Failure, Invocation};
+
+/// What a release is created with.
+struct NewRelease<'a> {
+    tag: &'a str,
+    target: Option<&'a str>,
+    title: &'a str,
+    notes: &'a str,
+    draft: bool,
+    prerelease: bool,
+}
+
+/// Perform a release steer.
+pub(super) fn perform(steer: &Steer, gh_config: &Path) -> Result<Option<SteerOutcome>, Failure> {
+    create(&new_release(steer), gh_config).map(Some)
+}
+
+/// The `gh` calls [`perform`] makes, in order.
+pub(super) fn plan(steer: &Steer) -> Vec<Invocation> {
+    vec![create_invocation(&new_release(steer))]
+}
+
+/// The release a release steer names.
+fn new_release(steer: &Steer) -> NewRelease<'_> {
+    let Steer::CreateRelease {
+        tag,
+        target,
+        title,
+        notes,
+        draft,
+        prerelease,
+    } = steer
+    else {
+        unreachable!("not a release steer: {}", steer.name());
+    };
+    NewRelease {
+        tag,
+        target: target.as_deref(),
+        title,
+        notes,
+        draft: *draft,
+        prerelease: *prerelease,
+    }
+}
+
+/// Create a release, and its tag if the tag doesn't exist yet, then report its URL.
+fn create(release: &NewRelease<'_>, gh_config: &Path) -> Result<SteerOutcome, Failure> {
+    let output = create_invocation(release).run(gh_config)?;
+    let url = output
+        .lines()
+        .map(str::trim)
+        .rfind(|l| !l.is_empty())
+        .ok_or_else(|| Failure::unread("gh printed no release URL".into()))?;
+    Ok(SteerOutcome::ReleaseCreated {
+        tag: release.tag.to_string(),
+        url: url.to_string(),
+    })
+}
+
+/// The `gh` call behind [`create`]; the notes go to stdin.
+fn create_invocation(release: &NewRelease<'_>) -> Invocation {
+    let mut args = vec!["release", "create", release.tag, "--title", release.title];
+    if let Some(target) = release.target {
+        args.extend(["--target", target]);
+    }
+    if release.draft {
+        args.push("--draft");
+    }
+    if release.prerelease {
+        args.push("--prerelease");
+    }
+    args.extend(["--notes-file", "-"]);
+    Invocation::new(args).with_input(release.notes.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notes_go_to_stdin() {
+        let invocation = create_invocation(&NewRelease {
+            tag: "v1.2.0",
+            target: Some("main"),
+            title: "v1.2.0",
+            notes: "- Fix widget crash\n",
+            draft: true,
+            prerelease: false,
+        });
+        assert_eq!(
+            invocation.args,
+            [
+                "release",
+                "create",
+                "v1.2.0",
+                "--title",
+                "v1.2.0",
+                "--target",
+                "main",
+                "--draft",
+                "--notes-file",
+                "-"
+            ]
+        );
+        assert_eq!(invocation.input.as_deref(), Some("- Fix widget crash\n"));
+    }
+}
@@ -0,0 +1,232 @@
+//! Review steers: request and withdraw reviewers, submit reviews.
+
+use std::path::Path;
+
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::model::{DiffSide, InlineComment, ReviewVerdict, Steer, SteerOutcome};
+
+use super::{Failure, Invocation};
+
+/// The subset of the REST pull request object that lists pending review requests.
+#[derive(Deserialize)]
+struct GhRequestedReviewers {
+    requested_reviewers: Vec<GhUser>,
+    requested_teams: Vec<GhTeam>,
+}
+
+#[derive(Deserialize)]
+struct GhUser {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct GhTeam {
+    slug: String,
+}
+
+/// Perform a review steer: request or withdraw reviewers, or submit a review.
+pub(super) fn perform(steer: &Steer, gh_config: &Path) -> Result<Option<SteerOutcome>, Failure> {
+    match steer {
+        Steer::RequestReview {
+            number,
+            reviewers,
+            teams,
+        } => request(*number, reviewers, teams, false, gh_config).map(Some),
+        Steer::WithdrawReviewRequest {
+            number,
+            reviewers,
+            teams,
+        } => request(*number, reviewers, teams, true, gh_config).map(Some),
+        Steer::SubmitReview {
+            number,
+            verdict,
+            body,
+            comments,
+        } => submit(*number, *verdict, body, comments, gh_config).map(Some),
+        _ => unreachable!("not a review steer: {}", steer.name()),
+    }
+}
+
+/// The `gh` calls [`perform`] makes, in order.
+pub(super) fn plan(steer: &Steer) -> Vec<Invocation> {
+    match steer {
+        Steer::RequestReview {
+            number,
+            reviewers,
+            teams,
+        } => vec![request_invocation(*number, reviewers, teams, false)],
+        Steer::WithdrawReviewRequest {
+            number,
+            reviewers,
+            teams,
+        } => vec![request_invocation(*number, reviewers, teams, true)],
+        Steer::SubmitReview {
+            number,
+            verdict,
+            body,
+            comments,
+        } => vec![submit_invocation(*number, *verdict, body, comments)],
+        _ => unreachable!("not a review steer: {}", steer.name()),
+    }
+}
+
+/// Request reviews from users and teams (`withdraw = false`),
+/// or withdraw pending requests (`withdraw = true`).
+///
+/// Goes through the REST API: both directions respond with the updated PR,
+/// so the outcome records the review requests left standing.
+fn request(
+    number: u64,
+    reviewers: &[String],
+    teams: &[String],
+    withdraw: bool,
+    gh_config: &Path,
+) -> Result<SteerOutcome, Failure> {
+    let json = request_invocation(number, reviewers, teams, withdraw).run(gh_config)?;
+    let pr: GhRequestedReviewers = serde_json::from_str(&json)
+        .map_err(|e| Failure::unread(format!("failed to parse requested reviewers: {e}")))?;
+    Ok(SteerOutcome::ReviewRequests {
+        reviewers: pr
+            .requested_reviewers
+            .into_iter()
+            .map(|u| u.login)
+            .collect(),
+        teams: pr.requested_teams.into_iter().map(|t| t.slug).collect(),
+    })
+}
+
+/// The `gh` call behind [`request`].
+fn request_invocation(
+    number: u64,
+    reviewers: &[String],
+    teams: &[String],
+    withdraw: bool,
+) -> Invocation {
+    let endpoint = format!("repos/{{owner}}/{{repo}}/pulls/{number}/requested_reviewers");
+    let method = if withdraw { "DELETE" } else { "POST" };
+
+    let mut args = vec![
+        "api".to_string(),
+        endpoint,
+        "--method".into(),
+        method.into(),
+    ];
+    for reviewer in reviewers {
+        args.extend(["-f".into(), format!("reviewers[]={reviewer}")]);
+    }
+    for team in teams {
+        args.extend(["-f".into(), format!("team_reviewers[]={team}")]);
+    }
+    Invocation::new(args)
+}
+
+/// JSON shape of a submitted review from the REST API.
+#[derive(Deserialize)]
+struct GhReview {
+    id: u64,
+    html_url: String,
+}
+
+/// Submit a review with a verdict, summary, and inline comments as one API call.
+fn submit(
+    number: u64,
+    verdict: ReviewVerdict,
+    body: &str,
+    comments: &[InlineComment],
+    gh_config: &Path,
+) -> Result<SteerOutcome, Failure> {
+    let json = submit_invocation(number, verdict, body, comments).run(gh_config)?;
+    let review: GhReview = serde_json::from_str(&json)
+        .map_err(|e| Failure::unread(format!("failed to parse review: {e}")))?;
+    Ok(SteerOutcome::ReviewSubmitted {
+        id: review.id,
+        url: review.html_url,
+    })
+}
+
+/// The `gh` call behind [`submit`]; the review goes to stdin as JSON.
+fn submit_invocation(
+    number: u64,
+    verdict: ReviewVerdict,
+    body: &str,
+    comments: &[InlineComment],
+) -> Invocation {
+    let request = review_request(verdict, body, comments);
+    Invocation::new([
+        "api",
+        &format!("repos/{{owner}}/{{repo}}/pulls/{number}/reviews"),
+        "--method",
+        "POST",
+        "--input",
+        "-",
+    ])
+    .with_input(format!("{request:#}"))
+}
+
+/// Build the REST request body for creating a review.
+fn review_request(
+    verdict: ReviewVerdict,
+    body: &str,
+    comments: &[InlineComment],
+) -> serde_json::Value {
+    let event = match verdict {
+        ReviewVerdict::Approve => "APPROVE",
+        ReviewVerdict::RequestChanges => "REQUEST_CHANGES",
+        ReviewVerdict::Comment => "COMMENT",
+    };
+    let comments: Vec<serde_json::Value> = comments
+        .iter()
+        .map(|c| {
+            let side = match c.side {
+                DiffSide::Left => "LEFT",
+                DiffSide::Right => "RIGHT",
+            };
+            let mut comment = json!({
+                "path": c.path,
+                "line": c.line,
+                "side": side,
+                "body": c.body,
+            });
+            if let Some(start) = c.start_line {
+                comment["start_line"] = json!(start);
+                comment["start_side"] = json!(side);
+            }
+            comment
+        })
+        .collect();
+    json!({ "body": body, "event": event, "comments": comments })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn review_request_shapes_ranges() {
+        let comments = vec![
+            InlineComment {
+                path: "src/widget.rs".into(),
+                line: 12,
+                start_line: Some(10),
+                side: DiffSide::Right,
+                body: "Extract this.".into(),
+            },
+            InlineComment {
+                path: "src/lib.rs".into(),
+                line: 3,
+                start_line: None,
+                side: DiffSide::Left,
+                body: "Why remove?".into(),
+            },
+        ];
+        let request = review_request(ReviewVerdict::RequestChanges, "A few things.", &comments);
+
+        assert_eq!(request["event"], "REQUEST_CHANGES");
+        assert_eq!(request["comments"][0]["start_line"], 10);
+        assert_eq!(request["comments"][0]["start_side"], "RIGHT");
+        assert_eq!(request["comments"][1]["side"], "LEFT");
+        assert!(request["comments"][1].get("start_line").is_none());
+    }
+}
@@ -0,0 +1,222 @@
+//! Review thread steers: resolve and unresolve inline review threads.
+//!
+//! Threads only exist in GraphQL, while helm knows review comments by their
+//! REST ID (the same ID `comment --reply-to` takes). So a thread steer first
+//! finds the thread holding the comment, then resolves or unresolves it by node ID.
+
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::model::{Steer, SteerOutcome};
+
+use super::{Failure, Invocation};
+
+/// Stands in for the thread's node ID until the lookup has run.
+const THREAD_ID: &str = "<thread id>";
+
+const THREADS_QUERY: &str = "
+query($owner: String!, $repo: String!, $number: Int!, $endCursor: String) {
+  repository(owner: $owner, name: $repo) {
+    pullRequest(number: $number) {
+      reviewThreads(first: 100, after: $endCursor) {
+        nodes { id comments(first: 100) { nodes { databaseId } } }
+        pageInfo { hasNextPage endCursor }
+      }
+    }
+  }
+}";
+
+const RESOLVE_MUTATION: &str = "
+mutation($threadId: ID!) {
+  resolveReviewThread(input: {threadId: $threadId}) { thread { id } }
+}";
+
+const UNRESOLVE_MUTATION: &str = "
+mutation($threadId: ID!) {
+  unresolveReviewThread(input: {threadId: $threadId}) { thread { id } }
+}";
+
+/// One page of the threads query, as `gh api graphql --paginate` prints it.
+#[derive(Deserialize)]
+struct GhThreadsPage {
+    data: GhData,
+}
+
+#[derive(Deserialize)]
+struct GhData {
+    repository: GhRepository,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhRepository {
+    pull_request: GhPullRequest,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhPullRequest {
+    review_threads: GhConnection<GhThread>,
+}
+
+#[derive(Deserialize)]
+struct GhConnection<T> {
+    nodes: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct GhThread {
+    id: String,
+    comments: GhConnection<GhThreadComment>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhThreadComment {
+    database_id: Option<u64>,
+}
+
+/// Perform a thread steer: resolve or unresolve.
+pub(super) fn perform(steer: &Steer, gh_config: &Path) -> Result<Option<SteerOutcome>, Failure> {
+    let (number, comment_id, resolved) = thread(steer);
+    set_resolved(number, comment_id, resolved, gh_config).map(Some)
+}
+
+/// The `gh` calls [`perform`] makes, in order.
+pub(super) fn plan(steer: &Steer) -> Vec<Invocation> {
+    let (number, _, resolved) = thread(steer);
+    plan_set_resolved(number, resolved)
+}
+
+/// The PR, comment, and resolution a thread steer names.
+fn thread(steer: &Steer) -> (u64, u64, bool) {
+    match steer {
+        Steer::ResolveThread { number, comment_id } => (*number, *comment_id, true),
+        Steer::UnresolveThread { number, comment_id } => (*number, *comment_id, false),
+        _ => unreachable!("not a thread steer: {}", steer.name()),
+    }
+}
+
+/// Resolve (`resolved = true`) or unresolve the thread holding `comment_id` on PR `number`.
+fn set_resolved(
+    number: u64,
+    comment_id: u64,
+    resolved: bool,
+    gh_config: &Path,
+) -> Result<SteerOutcome, Failure> {
+    let json = threads_invocation(number).run(gh_config)?;
+    let thread_id = find_thread(&json, comment_id)?.ok_or_else(|| {
+        format!("no review thread on PR #{number} holds review comment {comment_id}")
+    })?;
+
+    resolve_invocation(&thread_id, resolved).run(gh_config)?;
+    Ok(SteerOutcome::ThreadUpdated { thread_id })
+}
+
+/// The `gh` calls [`set_resolved`] makes, in order.
+fn plan_set_resolved(number: u64, resolved: bool) -> Vec<Invocation> {
+    vec![
+        threads_invocation(number),
+        resolve_invocation(THREAD_ID, resolved),
+    ]
+}
+
+fn threads_invocation(number: u64) -> Invocation {
+    Invocation::new([
+        "api".to_string(),
+        "graphql".into(),
+        "--paginate".into(),
+        "-F".into(),
+        "owner={owner}".into(),
+        "-F".into(),
+        "repo={repo}".into(),
+        "-F".into(),
+        format!("number={number}"),
+        "-f".into(),
+        format!("query={THREADS_QUERY}"),
+    ])
+}
+
+fn resolve_invocation(thread_id: &str, resolved: bool) -> Invocation {
+    let mutation = if resolved {
+        RESOLVE_MUTATION
+    } else {
+        UNRESOLVE_MUTATION
+    };
+    Invocation::new([
+        "api".to_string(),
+        "graphql".into(),
+        "-f".into(),
+        format!("threadId={thread_id}"),
+        "-f".into(),
+        format!("query={mutation}"),
+    ])
+}
+
+/// Find the node ID of the thread holding `comment_id` across all pages.
+fn find_thread(json: &str, comment_id: u64) -> Result<Option<String>, String> {
+    for page in serde_json::Deserializer::from_str(json).into_iter::<GhThreadsPage>() {
+        let page = page.map_err(|e| format!("failed to parse review threads: {e}"))?;
+        let found = page
+            .data
+            .repository
+            .pull_request
+            .review_threads
+            .nodes
+            .into_iter()
+            .find(|t| {
+                t.comments
+                    .nodes
+                    .iter()
+                    .any(|c| c.database_id == Some(comment_id))
+            });
+        if let Some(thread) = found {
+            return Ok(Some(thread.id));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(threads: &str) -> String {
+        format!(
+            r#"{{"data":{{"repository":{{"pullRequest":{{"reviewThreads":{{"nodes":[{threads}],"pageInfo":{{"hasNextPage":false,"endCursor":null}}}}}}}}}}}}"#
+        )
+    }
+
+    #[test]
+    fn find_thread_by_any_comment_across_pages() {
+        let json = format!(
+            "{}\n{}",
+            page(r#"{"id":"PRRT_a","comments":{"nodes":[{"databaseId":1}]}}"#),
+            page(r#"{"id":"PRRT_b","comments":{"nodes":[{"databaseId":2},{"databaseId":3}]}}"#),
+        );
+        assert_eq!(find_thread(&json, 3).unwrap().as_deref(), Some("PRRT_b"));
+        assert_eq!(find_thread(&json, 1).unwrap().as_deref(), Some("PRRT_a"));
+        assert_eq!(find_thread(&json, 9).unwrap(), None);
+    }
+
+    #[test]
+    fn plan_resolves_looked_up_thread() {
+        let invocations = plan(&Steer::UnresolveThread {
+            number: 45,
+            comment_id: 3,
+        });
+        assert!(invocations[0].args.contains(&"number=45".to_string()));
+        assert!(
+            invocations[1]
+                .args
+                .contains(&format!("threadId={THREAD_ID}"))
+        );
+        assert!(
+            invocations[1]
+                .args
+                .iter()
+                .any(|a| a.contains("unresolveReviewThread"))
+        );
+    }
+}
@@ -0,0 +1,220 @@
+//! Triage steers: labels, assignees, and milestones on issues and PRs.
+//!
+//! Like edits, each change goes through `gh issue edit` or `gh pr edit`,
+//! bracketed by reads of the triage fields so the outcome shows what moved.
+
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::model::{Steer, SteerOutcome, Subject, TriageSnapshot};
+
+use super::{Failure, Invocation};
+
+/// One triage change.
+enum Change<'a> {
+    Labels {
+        add: &'a [String],
+        remove: &'a [String],
+    },
+    Assignees {
+        add: &'a [String],
+        remove: &'a [String],
+    },
+    /// `None` clears the milestone.
+    Milestone(Option<&'a str>),
+}
+
+/// JSON shape for `gh issue view --json labels,assignees,milestone` (and `gh pr view`).
+#[derive(Deserialize)]
+struct GhTriage {
+    labels: Vec<GhLabel>,
+    assignees: Vec<GhUser>,
+    milestone: Option<GhMilestone>,
+}
+
+#[derive(Deserialize)]
+struct GhLabel {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct GhUser {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct GhMilestone {
+    title: String,
+}
+
+/// Perform a triage steer: labels, assignees, or milestone.
+pub(super) fn perform(steer: &Steer, gh_config: &Path) -> Result<Option<SteerOutcome>, Failure> {
+    let (subject, number, change) = change(steer);
+    triage(subject, number, &change, gh_config).map(Some)
+}
+
+/// The `gh` calls [`perform`] makes, in order.
+pub(super) fn plan(steer: &Steer) -> Vec<Invocation> {
+    let (subject, number, change) = change(steer);
+    plan_triage(subject, number, &change)
+}
+
+/// The issue or PR a triage steer names, and the change it makes.
+fn change(steer: &Steer) -> (Subject, u64, Change<'_>) {
+    match steer {
+        Steer::Label {
+            number,
+            subject,
+            add,
+            remove,
+        } => (*subject, *number, Change::Labels { add, remove }),
+        Steer::Assign {
+            number,
+            subject,
+            add,
+            remove,
+        } => (*subject, *number, Change::Assignees { add, remove }),
+        Steer::SetMilestone {
+            number,
+            subject,
+            milestone,
+        } => (*subject, *number, Change::Milestone(milestone.as_deref())),
+        _ => unreachable!("not a triage steer: {}", steer.name()),
+    }
+}
+
+/// Apply a triage change, recording the fields before and after.
+fn triage(
+    subject: Subject,
+    number: u64,
+    change: &Change<'_>,
+    gh_config: &Path,
+) -> Result<SteerOutcome, Failure> {
+    let before = Box::new(fetch_snapshot(subject, number, gh_config)?);
+
+    edit_invocation(subject, number, change).run(gh_config)?;
+
+    // The change has landed — failing to read it back must not lose the record.
+    let after = fetch_snapshot(subject, number, gh_config)
+        .ok()
+        .map(Box::new);
+    Ok(SteerOutcome::Triaged { before, after })
+}
+
+/// The `gh` calls [`triage`] makes, in order.
+fn plan_triage(subject: Subject, number: u64, change: &Change<'_>) -> Vec<Invocation> {
+    vec![
+        snapshot_invocation(subject, number),
+        edit_invocation(subject, number, change),
+        snapshot_invocation(subject, number),
+    ]
+}
+
+fn edit_invocation(subject: Subject, number: u64, change: &Change<'_>) -> Invocation {
+    let num = number.to_string();
+    let mut args = vec![gh_subcommand(subject), "edit", &num];
+    match change {
+        Change::Labels { add, remove } => {
+            for label in *add {
+                args.extend(["--add-label", label]);
+            }
+            for label in *remove {
+                args.extend(["--remove-label", label]);
+            }
+        }
+        Change::Assignees { add, remove } => {
+            for user in *add {
+                args.extend(["--add-assignee", user]);
+            }
+            for user in *remove {
+                args.extend(["--remove-assignee", user]);
+            }
+        }
+        Change::Milestone(Some(milestone)) => args.extend(["--milestone", milestone]),
+        Change::Milestone(None) => args.push("--remove-milestone"),
+    }
+    Invocation::new(args)
+}
+
+fn fetch_snapshot(
+    subject: Subject,
+    number: u64,
+    gh_config: &Path,
+) -> Result<TriageSnapshot, String> {
+    let json = snapshot_invocation(subject, number).run(gh_config)?;
+    let view: GhTriage = serde_json::from_str(&json).map_err(|e| {
+        format!(
+            "failed to parse gh {} view output: {e}",
+            gh_subcommand(subject)
+        )
+    })?;
+    Ok(TriageSnapshot {
+        labels: view.labels.into_iter().map(|l| l.name).collect(),
+        assignees: view.assignees.into_iter().map(|u| u.login).collect(),
+        milestone: view.milestone.map(|m| m.title),
+    })
+}
+
+fn snapshot_invocation(subject: Subject, number: u64) -> Invocation {
+    Invocation::new([
+        gh_subcommand(subject),
+        "view",
+        &number.to_string(),
+        "--json",
+        "labels,assignees,milestone",
+    ])
+}
+
+pub(super) fn gh_subcommand(subject: Subject) -> &'static str {
+    match subject {
+        Subject::Issue => "issue",
+        Subject::PullRequest => "pr",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_add_and_remove_in_one_edit() {
+        let add = vec!["bug".to_string()];
+        let remove = vec!["triage".to_string()];
+        let invocation = edit_invocation(
+            Subject::Issue,
+            42,
+            &Change::Labels {
+                add: &add,
+                remove: &remove,
+            },
+        );
+        assert_eq!(
+            invocation.args,
+            [
+                "issue",
+                "edit",
+                "42",
+                "--add-label",
+                "bug",
+                "--remove-label",
+                "triage"
+            ]
+        );
+    }
+
+    #[test]
+    fn clearing_milestone_uses_remove_flag() {
+        let invocation = edit_invocation(Subject::PullRequest, 7, &Change::Milestone(None));
+        assert_eq!(invocation.args, ["pr", "edit", "7", "--remove-milestone"]);
+    }
+
+    #[test]
+    fn snapshot_without_milestone_parses() {
+        let json =
+            r#"{"labels":[{"name":"bug"}],"assignees":[{"login":"dyreby"}],"milestone":null}"#;
+        let view: GhTriage = serde_json::from_str(json).unwrap();
+        assert!(view.milestone.is_none());
+        assert_eq!(view.assignees[0].login, "dyreby");
+    }
+}
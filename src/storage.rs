@@ -7,20 +7,29 @@
 //! ```
 //!
 //! The schema is initialised on `create_voyage` and versioned via
-//! `PRAGMA user_version`. Connections always enable foreign key enforcement.
+//! `PRAGMA user_version`. Older databases are migrated forward when opened.
+//! Connections always enable foreign key enforcement.
 
-use std::{fmt::Write as _, fs, io, path::PathBuf};
+use std::{fmt::Write as _, fs, io, path::PathBuf, time::Duration};
 
-use rusqlite::Connection;
+use rusqlite::{Connection, OpenFlags, backup::Backup};
 use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
-use crate::model::Payload;
+use crate::{model::Payload, signing::Keys};
 
+mod chain;
+mod denial;
+mod journal;
 mod logbook;
+mod search;
+mod signature;
 mod slate;
 mod voyage;
 
+pub use chain::{ChainProblem, ChainReport};
+pub use search::{HitSource, SearchFilter, SearchHit};
+
 /// DDL run once when a voyage database is created.
 ///
 /// Sets the schema version and creates all tables.
@@ -76,6 +85,88 @@ CREATE TABLE bearing_observations (
 );
 ";
 
+/// Schema migrations, applied in order after `SCHEMA_DDL`.
+///
+/// `MIGRATIONS[i]` upgrades a database from `user_version = i + 1` to `i + 2`.
+/// Each runs in its own transaction together with the version bump.
+const MIGRATIONS: &[&str] = &[
+    // 2: steer journal — steers are sealed into a pending row before they run.
+    "
+    CREATE TABLE pending_steers (
+        id         INTEGER PRIMARY KEY AUTOINCREMENT,
+        started_at TEXT NOT NULL,
+        identity   TEXT NOT NULL,
+        action     TEXT NOT NULL,
+        summary    TEXT NOT NULL,
+        role       TEXT NOT NULL,
+        method     TEXT NOT NULL
+    );
+
+    CREATE TABLE pending_observations (
+        pending_id    INTEGER NOT NULL REFERENCES pending_steers(id),
+        target        TEXT NOT NULL,
+        artifact_hash TEXT NOT NULL REFERENCES artifacts(hash),
+        observed_at   TEXT NOT NULL
+    );
+    ",
+    // 3: proposals — pending steers awaiting a second identity's approval.
+    "
+    ALTER TABLE pending_steers ADD COLUMN status TEXT NOT NULL DEFAULT 'performing'
+        CHECK(status IN ('performing', 'proposed'));
+    ALTER TABLE pending_steers ADD COLUMN approval TEXT;
+    ALTER TABLE logbook ADD COLUMN approval TEXT;
+    ",
+    // 4: policy denials — steers refused before they were performed.
+    "
+    CREATE TABLE denials (
+        id        INTEGER PRIMARY KEY AUTOINCREMENT,
+        denied_at TEXT NOT NULL,
+        identity  TEXT NOT NULL,
+        action    TEXT NOT NULL,
+        summary   TEXT NOT NULL,
+        role      TEXT NOT NULL,
+        method    TEXT NOT NULL,
+        reason    TEXT NOT NULL
+    );
+    ",
+    // 5: search index — see `search`. Existing voyages are backfilled by `migrate`.
+    "
+    CREATE VIRTUAL TABLE search_index USING fts5(
+        text,
+        source      UNINDEXED,
+        logbook_id  UNINDEXED,
+        observation UNINDEXED,
+        identity    UNINDEXED,
+        at          UNINDEXED,
+        tokenize = 'porter unicode61'
+    );
+    ",
+    // 6: hash chain — see `chain`. Existing entries are chained by `migrate`.
+    "
+    ALTER TABLE logbook ADD COLUMN prev_hash TEXT;
+    ALTER TABLE logbook ADD COLUMN hash TEXT;
+    ALTER TABLE voyage ADD COLUMN logbook_head TEXT;
+    ",
+    // 7: entry signatures — see `signature`. Entries recorded before stay unsigned.
+    "
+    ALTER TABLE logbook ADD COLUMN signature TEXT;
+    ",
+    // 8: where signing began, so entries recorded before it can pass `verify` unsigned.
+    "
+    ALTER TABLE voyage ADD COLUMN signed_from INTEGER NOT NULL DEFAULT 1;
+    UPDATE voyage SET signed_from = (SELECT COALESCE(MAX(id), 0) + 1 FROM logbook);
+    ",
+];
+
+/// The schema version `migrate` brings every voyage database up to.
+const SCHEMA_VERSION: usize = MIGRATIONS.len() + 1;
+
+/// The schema version that introduced `search_index`.
+const SEARCH_INDEX_VERSION: usize = 5;
+
+/// The schema version that introduced the logbook hash chain.
+const HASH_CHAIN_VERSION: usize = 6;
+
 /// Errors that can occur during storage operations.
 #[derive(Debug, thiserror::Error)]
 pub enum StorageError {
@@ -85,6 +176,15 @@ pub enum StorageError {
     #[error("voyage already exists: {0}")]
     VoyageAlreadyExists(Uuid),
 
+    #[error("pending steer not found: {0}")]
+    PendingSteerNotFound(i64),
+
+    #[error("no proposal awaiting approval: {0}")]
+    ProposalNotFound(i64),
+
+    #[error("logbook entry not found: {0}")]
+    EntryNotFound(usize),
+
     #[error("database error: {0}")]
     Db(#[from] rusqlite::Error),
 
@@ -97,6 +197,9 @@ pub enum StorageError {
     #[error("compression error: {0}")]
     Compression(String),
 
+    #[error("signing error: {0}")]
+    Signing(String),
+
     #[error("time parse error: {0}")]
     TimeParse(String),
 }
@@ -106,6 +209,8 @@ pub type Result<T> = core::result::Result<T, StorageError>;
 /// SQLite-backed storage for voyages and logbooks.
 pub struct Storage {
     root: PathBuf,
+    keys: Option<Keys>,
+    read_only: bool,
 }
 
 impl Storage {
@@ -115,7 +220,33 @@ impl Storage {
     pub fn new(root: impl Into<PathBuf>) -> Result<Self> {
         let root = root.into();
         fs::create_dir_all(&root)?;
-        Ok(Self { root })
+        Ok(Self {
+            root,
+            keys: None,
+            read_only: false,
+        })
+    }
+
+    /// Sign recorded entries with these keys, and check signatures on verify.
+    ///
+    /// Without keys, entries are recorded unsigned and signatures go unchecked.
+    #[must_use]
+    pub fn with_keys(mut self, keys: Keys) -> Self {
+        self.keys = Some(keys);
+        self
+    }
+
+    /// The same storage, opening voyages read-only: any write fails. For dry
+    /// runs, which must leave voyages untouched.
+    ///
+    /// A voyage whose schema predates this build is read from an upgraded
+    /// in-memory copy; its file is never migrated.
+    pub fn read_only(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+            keys: self.keys.clone(),
+            read_only: true,
+        }
     }
 
     /// Returns the default storage root: `~/.helm/voyages/`.
@@ -131,17 +262,69 @@ impl Storage {
     ///
     /// Returns [`StorageError::VoyageNotFound`] if the file does not exist.
     /// Enables foreign key enforcement on every connection.
+    /// Read-only storage opens the file read-only and migrates only an
+    /// in-memory copy.
     fn open_voyage(&self, id: Uuid) -> Result<Connection> {
         let path = self.voyage_path(id);
         if !path.exists() {
             return Err(StorageError::VoyageNotFound(id));
         }
-        let conn = Connection::open(&path)?;
+        let conn = if self.read_only {
+            Connection::open_with_flags(
+                &path,
+                OpenFlags::SQLITE_OPEN_READ_ONLY
+                    | OpenFlags::SQLITE_OPEN_URI
+                    | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+            )?
+        } else {
+            Connection::open(&path)?
+        };
+        let version = schema_version(&conn)?;
+        let conn = if self.read_only && version != 0 && version < SCHEMA_VERSION {
+            // Upgrade a private copy, so the file on disk stays as it was.
+            let mut copy = Connection::open_in_memory()?;
+            Backup::new(&conn, &mut copy)?.run_to_completion(256, Duration::ZERO, None)?;
+            copy
+        } else {
+            conn
+        };
         conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+        migrate(&conn)?;
         Ok(conn)
     }
 }
 
+/// Bring a voyage database up to the latest schema version.
+///
+/// A `user_version` of 0 means the file was never initialised by helm;
+/// it is left alone and fails on first use instead.
+///
+/// Creating `search_index` also indexes what the voyage already holds, and
+/// adding the hash chain links the entries already recorded, each in the
+/// same transaction as its migration.
+fn migrate(conn: &Connection) -> Result<()> {
+    let version = schema_version(conn)?;
+    if version == 0 {
+        return Ok(());
+    }
+    for (i, ddl) in MIGRATIONS.iter().enumerate().skip(version - 1) {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute_batch(ddl)?;
+        match i + 2 {
+            SEARCH_INDEX_VERSION => search::backfill(&tx)?,
+            HASH_CHAIN_VERSION => chain::backfill(&tx)?,
+            _ => {}
+        }
+        tx.pragma_update(None, "user_version", i + 2)?;
+        tx.commit()?;
+    }
+    Ok(())
+}
+
+fn schema_version(conn: &Connection) -> Result<usize> {
+    Ok(conn.query_row("PRAGMA user_version", [], |row| row.get(0))?)
+}
+
 /// Compress `data` with zstd at level 3.
 fn compress(data: &[u8]) -> Result<Vec<u8>> {
     zstd::encode_all(data, 3).map_err(|e| StorageError::Compression(e.to_string()))
@@ -179,11 +362,16 @@ fn store_artifact(conn: &Connection, payload: &Payload) -> Result<String> {
 
 /// Load and decompress a payload artifact from the database by hash.
 fn load_artifact(conn: &Connection, hash: &str) -> Result<Payload> {
+    let json = load_artifact_json(conn, hash)?;
+    Ok(serde_json::from_slice(&json)?)
+}
+
+/// Load and decompress an artifact's JSON without decoding it.
+fn load_artifact_json(conn: &Connection, hash: &str) -> Result<Vec<u8>> {
     let compressed: Vec<u8> = conn.query_row(
         "SELECT data FROM artifacts WHERE hash = ?1",
         rusqlite::params![hash],
         |row| row.get(0),
     )?;
-    let json = decompress(&compressed)?;
-    Ok(serde_json::from_slice(&json)?)
+    decompress(&compressed)
 }
@@ -0,0 +1,584 @@
+//! Hash chain: makes the logbook tamper-evident.
+//!
+//! Each `logbook` row stores `hash`, the SHA-256 of its canonical form, and
+//! `prev_hash`, the hash of the entry before it. The canonical form covers
+//! `prev_hash` and every column of the row and its `bearing_observations`,
+//! artifact hashes included. The voyage row stores `logbook_head`, the hash
+//! of the last entry.
+//!
+//! `verify_logbook` recomputes all of it, and re-hashes each bearing's
+//! artifacts, so editing a row, deleting or reordering entries, or altering
+//! an observed payload all show up. The chain can't stop someone rewriting
+//! every hash after an edit; publishing the head somewhere else can, and so
+//! can signatures (see `signature`), which `verify_logbook` checks too when
+//! storage has keys.
+
+use rusqlite::Connection;
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::{
+    Result, Storage, decompress,
+    logbook::{SealedRow, load_sealed_rows},
+    sha256_hex,
+    signature::{self, Flaw},
+};
+
+/// What `verify_logbook` found.
+#[derive(Debug, Clone)]
+pub struct ChainReport {
+    /// How many entries the logbook holds.
+    pub entries: usize,
+    /// The hash of the last entry, if any.
+    pub head: Option<String>,
+    /// How many entries carry a good signature.
+    pub signed: usize,
+    pub problems: Vec<ChainProblem>,
+}
+
+/// One way the logbook differs from what its hash chain and signatures recorded.
+///
+/// Entries and observations count from 1, as in `helm logbook show`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainProblem {
+    /// The entry has no hash: it was inserted around helm.
+    Unsealed { entry: usize },
+    /// The entry's contents no longer match its hash.
+    Rewritten { entry: usize },
+    /// The entry doesn't follow the one before it: entries were deleted or reordered.
+    Unlinked { entry: usize },
+    /// An observation's payload no longer matches its artifact hash.
+    ArtifactAltered { entry: usize, observation: usize },
+    /// An observation's artifact is gone.
+    ArtifactMissing { entry: usize, observation: usize },
+    /// The logbook doesn't end at the recorded head: entries were deleted from the end.
+    Truncated { head: Option<String> },
+    /// The entry carries no signature from `signer`, who has no key or recorded it before signing began.
+    Unsigned { entry: usize, signer: String },
+    /// The entry carries no signature, though `signer` has a key: it was stripped.
+    MissingSignature { entry: usize, signer: String },
+    /// The entry's signature wasn't made by `signer`'s key over this entry.
+    BadSignature { entry: usize, signer: String },
+    /// The entry is signed, but `signer` has no public key to check it against.
+    UnknownSigner { entry: usize, signer: String },
+}
+
+impl ChainProblem {
+    /// Whether this is only a missing signature or key, rather than evidence of tampering.
+    pub fn is_unverified(&self) -> bool {
+        matches!(self, Self::Unsigned { .. } | Self::UnknownSigner { .. })
+    }
+}
+
+impl Storage {
+    /// Check a voyage's logbook against its hash chain, and its signatures if storage has keys.
+    pub fn verify_logbook(&self, voyage_id: Uuid) -> Result<ChainReport> {
+        let conn = self.open_voyage(voyage_id)?;
+        let recorded_head = head(&conn)?;
+
+        let rows = {
+            let mut stmt = conn.prepare("SELECT id, prev_hash, hash FROM logbook ORDER BY id")?;
+            stmt.query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?
+        };
+
+        let mut problems = Vec::new();
+        let mut good_signatures = 0;
+        let mut previous: Option<String> = None;
+        for (i, (id, prev_hash, hash)) in rows.iter().enumerate() {
+            let entry = i + 1;
+            let Some(hash) = hash else {
+                problems.push(ChainProblem::Unsealed { entry });
+                previous = None;
+                continue;
+            };
+            if entry_hash(&conn, *id, prev_hash.as_deref())? != *hash {
+                problems.push(ChainProblem::Rewritten { entry });
+            }
+            if *prev_hash != previous {
+                problems.push(ChainProblem::Unlinked { entry });
+            }
+            problems.extend(check_artifacts(&conn, *id, entry)?);
+            if let Some(keys) = &self.keys {
+                match signature::check(&conn, keys, *id)? {
+                    (_, None) => good_signatures += 1,
+                    (signer, Some(Flaw::Unsigned)) => {
+                        problems.push(ChainProblem::Unsigned { entry, signer });
+                    }
+                    (signer, Some(Flaw::Missing)) => {
+                        problems.push(ChainProblem::MissingSignature { entry, signer });
+                    }
+                    (signer, Some(Flaw::Bad)) => {
+                        problems.push(ChainProblem::BadSignature { entry, signer });
+                    }
+                    (signer, Some(Flaw::NoKey)) => {
+                        problems.push(ChainProblem::UnknownSigner { entry, signer });
+                    }
+                }
+            }
+            previous = Some(hash.clone());
+        }
+
+        let head = rows.last().and_then(|(_, _, hash)| hash.clone());
+        if head != recorded_head {
+            problems.push(ChainProblem::Truncated {
+                head: recorded_head,
+            });
+        }
+
+        Ok(ChainReport {
+            entries: rows.len(),
+            head,
+            signed: good_signatures,
+            problems,
+        })
+    }
+}
+
+/// Link a freshly inserted logbook row onto the chain and advance the head.
+///
+/// Runs after its bearing observations are inserted, in the same transaction.
+pub(super) fn link(conn: &Connection, logbook_id: i64) -> Result<()> {
+    let prev_hash = head(conn)?;
+    let hash = entry_hash(conn, logbook_id, prev_hash.as_deref())?;
+    conn.execute(
+        "UPDATE logbook SET prev_hash = ?1, hash = ?2 WHERE id = ?3",
+        rusqlite::params![prev_hash, hash, logbook_id],
+    )?;
+    conn.execute(
+        "UPDATE voyage SET logbook_head = ?1",
+        rusqlite::params![hash],
+    )?;
+    Ok(())
+}
+
+/// The hash of the last entry: what the next entry links to.
+pub(super) fn head(conn: &Connection) -> Result<Option<String>> {
+    Ok(
+        conn.query_row("SELECT logbook_head FROM voyage LIMIT 1", [], |row| {
+            row.get(0)
+        })?,
+    )
+}
+
+/// Chain every existing entry in order — for databases that predate the chain.
+pub(super) fn backfill(conn: &Connection) -> Result<()> {
+    let ids = {
+        let mut stmt = conn.prepare("SELECT id FROM logbook ORDER BY id")?;
+        stmt.query_map([], |row| row.get::<_, i64>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?
+    };
+    for id in ids {
+        link(conn, id)?;
+    }
+    Ok(())
+}
+
+/// The columns of a logbook row that its hash covers, as stored —
+/// the action and approval as their JSON text.
+pub(super) struct EntryRow {
+    pub recorded_at: String,
+    pub identity: String,
+    pub role: String,
+    pub method: String,
+    pub action: String,
+    pub summary: String,
+    pub approval: Option<String>,
+}
+
+/// What an entry's hash covers, in a fixed order.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CanonicalEntry<'a> {
+    prev_hash: Option<&'a str>,
+    recorded_at: &'a str,
+    identity: &'a str,
+    role: &'a str,
+    method: &'a str,
+    action: &'a str,
+    summary: &'a str,
+    approval: Option<&'a str>,
+    bearing: Vec<CanonicalObservation<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CanonicalObservation<'a> {
+    target: &'a str,
+    artifact_hash: &'a str,
+    observed_at: &'a str,
+}
+
+/// The canonical form of an entry, as the bytes its hash is taken over.
+///
+/// Built from columns as stored, so decoding and re-encoding can't change the bytes.
+/// An entry not yet inserted has one too, which is what lets it be signed
+/// before the transaction that records it.
+pub(super) fn canonical(
+    row: &EntryRow,
+    prev_hash: Option<&str>,
+    bearing: &[SealedRow],
+) -> Result<Vec<u8>> {
+    let entry = CanonicalEntry {
+        prev_hash,
+        recorded_at: &row.recorded_at,
+        identity: &row.identity,
+        role: &row.role,
+        method: &row.method,
+        action: &row.action,
+        summary: &row.summary,
+        approval: row.approval.as_deref(),
+        bearing: bearing
+            .iter()
+            .map(
+                |(target, artifact_hash, observed_at)| CanonicalObservation {
+                    target,
+                    artifact_hash,
+                    observed_at,
+                },
+            )
+            .collect(),
+    };
+    Ok(serde_json::to_vec(&entry)?)
+}
+
+/// The canonical form of a stored logbook row.
+pub(super) fn canonical_entry(
+    conn: &Connection,
+    logbook_id: i64,
+    prev_hash: Option<&str>,
+) -> Result<Vec<u8>> {
+    let row = conn.query_row(
+        "SELECT recorded_at, identity, role, method, action, summary, approval
+         FROM logbook WHERE id = ?1",
+        rusqlite::params![logbook_id],
+        |row| {
+            Ok(EntryRow {
+                recorded_at: row.get(0)?,
+                identity: row.get(1)?,
+                role: row.get(2)?,
+                method: row.get(3)?,
+                action: row.get(4)?,
+                summary: row.get(5)?,
+                approval: row.get(6)?,
+            })
+        },
+    )?;
+    let bearing = load_sealed_rows(
+        conn,
+        "SELECT target, artifact_hash, observed_at FROM bearing_observations
+         WHERE logbook_id = ?1
+         ORDER BY rowid",
+        rusqlite::params![logbook_id],
+    )?;
+    canonical(&row, prev_hash, &bearing)
+}
+
+fn entry_hash(conn: &Connection, logbook_id: i64, prev_hash: Option<&str>) -> Result<String> {
+    Ok(sha256_hex(&canonical_entry(conn, logbook_id, prev_hash)?))
+}
+
+/// Re-hash each artifact in an entry's bearing against the hash it is stored under.
+fn check_artifacts(conn: &Connection, logbook_id: i64, entry: usize) -> Result<Vec<ChainProblem>> {
+    let mut stmt = conn.prepare(
+        "SELECT b.artifact_hash, a.data
+         FROM bearing_observations b LEFT JOIN artifacts a ON a.hash = b.artifact_hash
+         WHERE b.logbook_id = ?1
+         ORDER BY b.rowid",
+    )?;
+    let rows = stmt
+        .query_map(rusqlite::params![logbook_id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Option<Vec<u8>>>(1)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut problems = Vec::new();
+    for (i, (hash, data)) in rows.into_iter().enumerate() {
+        let observation = i + 1;
+        match data {
+            None => problems.push(ChainProblem::ArtifactMissing { entry, observation }),
+            Some(compressed) => {
+                let intact = decompress(&compressed).is_ok_and(|json| sha256_hex(&json) == hash);
+                if !intact {
+                    problems.push(ChainProblem::ArtifactAltered { entry, observation });
+                }
+            }
+        }
+    }
+    Ok(problems)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::PathBuf;
+
+    use jiff::Timestamp;
+    use tempfile::TempDir;
+
+    use crate::{
+        model::{
+            DirectoryEntry, DirectoryListing, Observation, Observe, Payload, Provenance, Voyage,
+            VoyageStatus,
+        },
+        storage::{StorageError, logbook},
+    };
+
+    fn voyage_with_entries(count: usize) -> (TempDir, Storage, Voyage) {
+        let dir = TempDir::new().unwrap();
+        let storage = Storage::new(dir.path().join("voyages")).unwrap();
+        let voyage = Voyage {
+            id: Uuid::new_v4(),
+            intent: "Fix the widget".into(),
+            created_at: Timestamp::now(),
+            status: VoyageStatus::Active,
+        };
+        storage.create_voyage(&voyage).unwrap();
+
+        for i in 0..count {
+            let observation = Observation {
+                target: Observe::DirectoryTree {
+                    root: PathBuf::from("src/"),
+                    skip: vec![],
+                    max_depth: None,
+                },
+                payload: Payload::DirectoryTree {
+                    listings: vec![DirectoryListing {
+                        path: PathBuf::from("src/"),
+                        entries: vec![DirectoryEntry {
+                            name: format!("file{i}.rs"),
+                            is_dir: false,
+                            size_bytes: Some(42),
+                        }],
+                    }],
+                },
+                observed_at: Timestamp::now(),
+            };
+            storage.observe(voyage.id, &observation).unwrap();
+            storage
+                .record_log(
+                    voyage.id,
+                    &format!("step {i}"),
+                    "Checked in",
+                    &Provenance {
+                        identity: "alice".into(),
+                        role: "coder".into(),
+                        method: "conversation".into(),
+                    },
+                )
+                .unwrap();
+        }
+        (dir, storage, voyage)
+    }
+
+    fn raw(storage: &Storage, voyage: &Voyage) -> Connection {
+        Connection::open(storage.voyage_path(voyage.id)).unwrap()
+    }
+
+    #[test]
+    fn untouched_logbook_verifies() {
+        let (_dir, storage, voyage) = voyage_with_entries(3);
+        let report = storage.verify_logbook(voyage.id).unwrap();
+        assert_eq!(report.entries, 3);
+        assert!(report.head.is_some());
+        assert!(report.problems.is_empty(), "{:?}", report.problems);
+    }
+
+    #[test]
+    fn rewritten_entry_is_detected() {
+        let (_dir, storage, voyage) = voyage_with_entries(3);
+        raw(&storage, &voyage)
+            .execute(
+                "UPDATE logbook SET identity = 'mallory' WHERE id = (SELECT MIN(id) + 1 FROM logbook)",
+                [],
+            )
+            .unwrap();
+
+        let report = storage.verify_logbook(voyage.id).unwrap();
+        assert_eq!(report.problems, [ChainProblem::Rewritten { entry: 2 }]);
+    }
+
+    #[test]
+    fn deleted_and_reordered_entries_are_detected() {
+        let (_dir, storage, voyage) = voyage_with_entries(3);
+        let conn = raw(&storage, &voyage);
+        conn.execute_batch(
+            "DELETE FROM bearing_observations WHERE logbook_id = (SELECT MIN(id) FROM logbook);
+             DELETE FROM logbook WHERE id = (SELECT MIN(id) FROM logbook);",
+        )
+        .unwrap();
+        let report = storage.verify_logbook(voyage.id).unwrap();
+        assert_eq!(report.problems, [ChainProblem::Unlinked { entry: 1 }]);
+
+        conn.execute_batch(
+            "DELETE FROM bearing_observations WHERE logbook_id = (SELECT MAX(id) FROM logbook);
+             DELETE FROM logbook WHERE id = (SELECT MAX(id) FROM logbook);",
+        )
+        .unwrap();
+        let report = storage.verify_logbook(voyage.id).unwrap();
+        assert!(matches!(
+            report.problems.last(),
+            Some(ChainProblem::Truncated { .. })
+        ));
+    }
+
+    #[test]
+    fn altered_artifact_is_detected() {
+        let (_dir, storage, voyage) = voyage_with_entries(1);
+        let forged = super::super::compress(b"{\"kind\":\"hold\",\"hash\":\"x\"}").unwrap();
+        raw(&storage, &voyage)
+            .execute("UPDATE artifacts SET data = ?1", rusqlite::params![forged])
+            .unwrap();
+
+        let report = storage.verify_logbook(voyage.id).unwrap();
+        assert_eq!(
+            report.problems,
+            [ChainProblem::ArtifactAltered {
+                entry: 1,
+                observation: 1
+            }]
+        );
+    }
+
+    #[test]
+    fn signatures_are_checked_against_the_signer() {
+        let (_keys_dir, keys) = crate::signing::keys_for("alice");
+        let dir = TempDir::new().unwrap();
+        let storage = Storage::new(dir.path().join("voyages"))
+            .unwrap()
+            .with_keys(keys);
+        let voyage = Voyage {
+            id: Uuid::new_v4(),
+            intent: "Fix the widget".into(),
+            created_at: Timestamp::now(),
+            status: VoyageStatus::Active,
+        };
+        storage.create_voyage(&voyage).unwrap();
+        for identity in ["alice", "bob"] {
+            storage
+                .record_log(
+                    voyage.id,
+                    "checked in",
+                    "Checked in",
+                    &Provenance {
+                        identity: identity.into(),
+                        role: "coder".into(),
+                        method: "conversation".into(),
+                    },
+                )
+                .unwrap();
+        }
+
+        let report = storage.verify_logbook(voyage.id).unwrap();
+        assert_eq!(report.signed, 1);
+        assert_eq!(
+            report.problems,
+            [ChainProblem::Unsigned {
+                entry: 2,
+                signer: "bob".into()
+            }]
+        );
+
+        // Alice's signature, moved onto Bob's entry.
+        raw(&storage, &voyage)
+            .execute_batch(
+                "UPDATE logbook SET identity = 'alice'
+                 WHERE id = (SELECT MAX(id) FROM logbook);
+                 UPDATE logbook SET signature = (SELECT signature FROM logbook ORDER BY id LIMIT 1)
+                 WHERE id = (SELECT MAX(id) FROM logbook);",
+            )
+            .unwrap();
+        let report = storage.verify_logbook(voyage.id).unwrap();
+        assert!(report.problems.contains(&ChainProblem::BadSignature {
+            entry: 2,
+            signer: "alice".into()
+        }));
+    }
+
+    #[test]
+    fn a_signature_made_before_the_head_moved_is_refused() {
+        let (_keys_dir, keys) = crate::signing::keys_for("alice");
+        let dir = TempDir::new().unwrap();
+        let storage = Storage::new(dir.path().join("voyages"))
+            .unwrap()
+            .with_keys(keys);
+        let voyage = Voyage {
+            id: Uuid::new_v4(),
+            intent: "Fix the widget".into(),
+            created_at: Timestamp::now(),
+            status: VoyageStatus::Active,
+        };
+        storage.create_voyage(&voyage).unwrap();
+        let alice = Provenance {
+            identity: "alice".into(),
+            role: "coder".into(),
+            method: "conversation".into(),
+        };
+
+        let conn = raw(&storage, &voyage);
+        let row = logbook::new_row("{}".into(), "Checked in", &alice, None).unwrap();
+        let presigned = storage.presign(&conn, &row, None, &[]).unwrap().unwrap();
+        storage
+            .record_log(voyage.id, "moved on", "Moved on", &alice)
+            .unwrap();
+
+        let err = logbook::insert_entry(&conn, &row, &[], Some(&presigned)).unwrap_err();
+        assert!(matches!(err, StorageError::Signing(_)));
+        assert_eq!(storage.load_logbook(voyage.id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn a_stripped_signature_fails_verification() {
+        let (_keys_dir, keys) = crate::signing::keys_for("alice");
+        let dir = TempDir::new().unwrap();
+        let storage = Storage::new(dir.path().join("voyages"))
+            .unwrap()
+            .with_keys(keys);
+        let voyage = Voyage {
+            id: Uuid::new_v4(),
+            intent: "Fix the widget".into(),
+            created_at: Timestamp::now(),
+            status: VoyageStatus::Active,
+        };
+        storage.create_voyage(&voyage).unwrap();
+        let alice = Provenance {
+            identity: "alice".into(),
+            role: "coder".into(),
+            method: "conversation".into(),
+        };
+        storage
+            .record_log(voyage.id, "checked in", "Checked in", &alice)
+            .unwrap();
+
+        raw(&storage, &voyage)
+            .execute("UPDATE logbook SET signature = NULL", [])
+            .unwrap();
+        let report = storage.verify_logbook(voyage.id).unwrap();
+        let problem = ChainProblem::MissingSignature {
+            entry: 1,
+            signer: "alice".into(),
+        };
+        assert!(!problem.is_unverified());
+        assert_eq!(report.problems, [problem]);
+
+        // Entries from before the voyage could sign stay merely unsigned.
+        raw(&storage, &voyage)
+            .execute("UPDATE voyage SET signed_from = 2", [])
+            .unwrap();
+        let report = storage.verify_logbook(voyage.id).unwrap();
+        assert_eq!(
+            report.problems,
+            [ChainProblem::Unsigned {
+                entry: 1,
+                signer: "alice".into()
+            }]
+        );
+    }
+}
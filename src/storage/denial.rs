@@ -0,0 +1,168 @@
+//! Denial storage: steers the policy refused, kept for review.
+//!
+//! A denial touches nothing else — the slate stays as it was, so the
+//! same bearing can back a proposal or a different steer.
+
+use jiff::Timestamp;
+use uuid::Uuid;
+
+use crate::model::{Denial, Provenance, Steer};
+
+use super::{Result, Storage, StorageError};
+
+impl Storage {
+    /// Record that the policy refused a steer.
+    pub fn record_denial(
+        &self,
+        voyage_id: Uuid,
+        steer: &Steer,
+        summary: &str,
+        provenance: &Provenance,
+        reason: &str,
+    ) -> Result<()> {
+        let conn = self.open_voyage(voyage_id)?;
+        conn.execute(
+            "INSERT INTO denials (denied_at, identity, action, summary, role, method, reason)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            rusqlite::params![
+                Timestamp::now().to_string(),
+                provenance.identity,
+                serde_json::to_string(steer)?,
+                summary,
+                provenance.role,
+                provenance.method,
+                reason,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Load every recorded denial, oldest first.
+    pub fn load_denials(&self, voyage_id: Uuid) -> Result<Vec<Denial>> {
+        let conn = self.open_voyage(voyage_id)?;
+
+        let mut stmt = conn.prepare(
+            "SELECT denied_at, identity, action, summary, role, method, reason
+             FROM denials
+             ORDER BY id",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(DenialRow {
+                    denied_at: row.get(0)?,
+                    provenance: Provenance {
+                        identity: row.get(1)?,
+                        role: row.get(4)?,
+                        method: row.get(5)?,
+                    },
+                    action_json: row.get(2)?,
+                    summary: row.get(3)?,
+                    reason: row.get(6)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(Denial {
+                    steer: serde_json::from_str(&row.action_json)?,
+                    summary: row.summary,
+                    provenance: row.provenance,
+                    reason: row.reason,
+                    denied_at: row
+                        .denied_at
+                        .parse()
+                        .map_err(|e: jiff::Error| StorageError::TimeParse(e.to_string()))?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// A `denials` row as read, before decoding its JSON and timestamp columns.
+struct DenialRow {
+    denied_at: String,
+    provenance: Provenance,
+    action_json: String,
+    summary: String,
+    reason: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::TempDir;
+
+    use crate::model::{MergeStrategy, Voyage, VoyageStatus};
+
+    fn test_storage() -> (TempDir, Storage) {
+        let dir = TempDir::new().unwrap();
+        let storage = Storage::new(dir.path().join("voyages")).unwrap();
+        (dir, storage)
+    }
+
+    fn sample_voyage() -> Voyage {
+        Voyage {
+            id: Uuid::new_v4(),
+            intent: "Fix the widget".into(),
+            created_at: Timestamp::now(),
+            status: VoyageStatus::Active,
+        }
+    }
+
+    fn provenance() -> Provenance {
+        Provenance {
+            identity: "agent-x".into(),
+            role: "coder".into(),
+            method: "automated".into(),
+        }
+    }
+
+    fn sample_steer() -> Steer {
+        Steer::MergePullRequest {
+            number: 7,
+            strategy: MergeStrategy::Squash,
+            delete_branch: true,
+            expected_head: "abc123".into(),
+        }
+    }
+
+    #[test]
+    fn record_and_load_denials() {
+        let (_dir, storage) = test_storage();
+        let voyage = sample_voyage();
+        storage.create_voyage(&voyage).unwrap();
+
+        storage
+            .record_denial(
+                voyage.id,
+                &sample_steer(),
+                "Tests pass",
+                &provenance(),
+                "agent-x may not merge-pr",
+            )
+            .unwrap();
+
+        let denials = storage.load_denials(voyage.id).unwrap();
+        assert_eq!(denials.len(), 1);
+        assert_eq!(denials[0].steer.name(), "merge-pr");
+        assert_eq!(denials[0].summary, "Tests pass");
+        assert_eq!(denials[0].provenance.identity, "agent-x");
+        assert_eq!(denials[0].reason, "agent-x may not merge-pr");
+    }
+
+    #[test]
+    fn denial_leaves_logbook_and_journal_alone() {
+        let (_dir, storage) = test_storage();
+        let voyage = sample_voyage();
+        storage.create_voyage(&voyage).unwrap();
+
+        storage
+            .record_denial(voyage.id, &sample_steer(), "s", &provenance(), "denied")
+            .unwrap();
+
+        assert!(storage.load_logbook(voyage.id).unwrap().is_empty());
+        assert!(storage.load_pending_steers(voyage.id).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,676 @@
+//! Steer journal: the two-phase protocol around performing a steer.
+//!
+//! A steer crosses the collaborative boundary, which no local transaction
+//! can cover. So it is recorded in two phases, each one transaction:
+//! 1. `begin_steer` seals the slate into a pending row before the action runs.
+//! 2. `complete_steer` records the logbook entry from that row once the action
+//!    succeeded, or `abandon_steer` returns the sealed observations to the slate
+//!    if it failed.
+//!
+//! A pending row that outlives its invocation means helm was interrupted
+//! between the two phases. `load_pending_steers` surfaces it so someone can
+//! check GitHub and reconcile.
+//!
+//! Proposals are pending rows that wait before phase 2 starts:
+//! `propose_steer` journals the steer without performing it, and
+//! `approve_steer` moves it to performing once a second identity signs off.
+
+use jiff::Timestamp;
+use rusqlite::OptionalExtension;
+use uuid::Uuid;
+
+use crate::model::{
+    Approval, EntryKind, Observation, PendingStatus, PendingSteer, Provenance, Steer, SteerOutcome,
+};
+
+use super::{
+    Result, Storage, StorageError,
+    logbook::{decode_sealed_rows, insert_entry, load_sealed_rows, new_row, prune_artifacts},
+};
+
+impl Storage {
+    /// Journal a steer that is about to be performed.
+    ///
+    /// Seals the slate into the pending row and clears the slate.
+    /// Returns the pending row's ID, which completes or abandons it.
+    pub fn begin_steer(
+        &self,
+        voyage_id: Uuid,
+        steer: &Steer,
+        summary: &str,
+        provenance: &Provenance,
+    ) -> Result<i64> {
+        self.journal_steer(voyage_id, steer, summary, provenance, "performing")
+    }
+
+    /// Journal a steer for a second identity to approve, without performing it.
+    ///
+    /// Seals the slate into the proposal exactly like `begin_steer`, so the
+    /// approver sees the bearing the proposer decided on.
+    pub fn propose_steer(
+        &self,
+        voyage_id: Uuid,
+        steer: &Steer,
+        summary: &str,
+        provenance: &Provenance,
+    ) -> Result<i64> {
+        self.journal_steer(voyage_id, steer, summary, provenance, "proposed")
+    }
+
+    /// Sign off on a proposal and mark it performing.
+    ///
+    /// Only a proposal still awaiting approval can be approved, so two
+    /// approvers racing on the same proposal can't both perform it.
+    pub fn approve_steer(
+        &self,
+        voyage_id: Uuid,
+        pending_id: i64,
+        approval: &Approval,
+    ) -> Result<()> {
+        let conn = self.open_voyage(voyage_id)?;
+        let updated = conn.execute(
+            "UPDATE pending_steers SET status = 'performing', approval = ?2
+             WHERE id = ?1 AND status = 'proposed'",
+            rusqlite::params![pending_id, serde_json::to_string(approval)?],
+        )?;
+        if updated == 0 {
+            return Err(StorageError::ProposalNotFound(pending_id));
+        }
+        Ok(())
+    }
+
+    /// Return an approved steer whose action failed to awaiting approval.
+    pub fn withdraw_approval(&self, voyage_id: Uuid, pending_id: i64) -> Result<()> {
+        let conn = self.open_voyage(voyage_id)?;
+        let updated = conn.execute(
+            "UPDATE pending_steers SET status = 'proposed', approval = NULL
+             WHERE id = ?1 AND status = 'performing' AND approval IS NOT NULL",
+            rusqlite::params![pending_id],
+        )?;
+        if updated == 0 {
+            return Err(StorageError::PendingSteerNotFound(pending_id));
+        }
+        Ok(())
+    }
+
+    /// Insert a pending steer with the given status and seal the slate into it.
+    fn journal_steer(
+        &self,
+        voyage_id: Uuid,
+        steer: &Steer,
+        summary: &str,
+        provenance: &Provenance,
+        status: &str,
+    ) -> Result<i64> {
+        let action_json = serde_json::to_string(steer)?;
+
+        let mut conn = self.open_voyage(voyage_id)?;
+        let tx = conn.transaction()?;
+
+        tx.execute(
+            "INSERT INTO pending_steers
+             (started_at, identity, action, summary, role, method, status)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            rusqlite::params![
+                Timestamp::now().to_string(),
+                provenance.identity,
+                action_json,
+                summary,
+                provenance.role,
+                provenance.method,
+                status,
+            ],
+        )?;
+        let pending_id = tx.last_insert_rowid();
+
+        tx.execute(
+            "INSERT INTO pending_observations (pending_id, target, artifact_hash, observed_at)
+             SELECT ?1, target, artifact_hash, observed_at FROM slate ORDER BY rowid",
+            rusqlite::params![pending_id],
+        )?;
+        tx.execute("DELETE FROM slate", [])?;
+
+        tx.commit()?;
+        Ok(pending_id)
+    }
+
+    /// Record a journaled steer in the logbook and remove it from the journal.
+    ///
+    /// `outcome` is what the performed action produced, recorded alongside the steer.
+    /// The bearing is the slate as it was sealed by `begin_steer` or `propose_steer`,
+    /// and an approval, if any, is recorded with the entry.
+    pub fn complete_steer(
+        &self,
+        voyage_id: Uuid,
+        pending_id: i64,
+        outcome: Option<&SteerOutcome>,
+    ) -> Result<()> {
+        let mut conn = self.open_voyage(voyage_id)?;
+
+        // Everything the entry covers is read, and the entry signed, before
+        // the transaction opens; the pending row is fixed once journaled.
+        let (action_json, summary, provenance, approval) = conn
+            .query_row(
+                "SELECT action, summary, identity, role, method, approval
+                 FROM pending_steers WHERE id = ?1",
+                rusqlite::params![pending_id],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        Provenance {
+                            identity: row.get(2)?,
+                            role: row.get(3)?,
+                            method: row.get(4)?,
+                        },
+                        row.get::<_, Option<String>>(5)?,
+                    ))
+                },
+            )
+            .optional()?
+            .ok_or(StorageError::PendingSteerNotFound(pending_id))?;
+
+        let entry_json = serde_json::to_string(&EntryKind::Steer {
+            steer: serde_json::from_str(&action_json)?,
+            outcome: outcome.cloned(),
+        })?;
+        let approval: Option<Approval> = approval
+            .map(|json| serde_json::from_str(&json))
+            .transpose()?;
+        let observations = load_pending_rows(&conn, pending_id)?;
+        let row = new_row(entry_json, &summary, &provenance, approval.as_ref())?;
+        let presigned = self.presign(&conn, &row, approval.as_ref(), &observations)?;
+
+        let tx = conn.transaction()?;
+        insert_entry(&tx, &row, &observations, presigned.as_ref())?;
+        delete_pending(&tx, pending_id)?;
+        prune_artifacts(&tx)?;
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Drop a journaled steer whose action did not happen, or a rejected proposal.
+    ///
+    /// Its sealed observations return to the slate, unless the same target
+    /// has been observed again since — the newer observation wins.
+    pub fn abandon_steer(&self, voyage_id: Uuid, pending_id: i64) -> Result<()> {
+        let mut conn = self.open_voyage(voyage_id)?;
+        let tx = conn.transaction()?;
+
+        let exists: bool = tx.query_row(
+            "SELECT EXISTS(SELECT 1 FROM pending_steers WHERE id = ?1)",
+            rusqlite::params![pending_id],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            return Err(StorageError::PendingSteerNotFound(pending_id));
+        }
+
+        for (target, artifact_hash, observed_at) in load_pending_rows(&tx, pending_id)? {
+            tx.execute(
+                "INSERT OR IGNORE INTO slate (target, artifact_hash, observed_at)
+                 VALUES (?1, ?2, ?3)",
+                rusqlite::params![target, artifact_hash, observed_at],
+            )?;
+        }
+        delete_pending(&tx, pending_id)?;
+        prune_artifacts(&tx)?;
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Load the observations sealed into a pending steer's bearing, in slate order.
+    pub fn load_pending_bearing(
+        &self,
+        voyage_id: Uuid,
+        pending_id: i64,
+    ) -> Result<Vec<Observation>> {
+        let conn = self.open_voyage(voyage_id)?;
+        let rows = load_pending_rows(&conn, pending_id)?;
+        decode_sealed_rows(&conn, rows)
+    }
+
+    /// Load the steers journaled but not yet completed or abandoned, oldest first.
+    ///
+    /// Includes proposals awaiting approval; `PendingSteer::status` tells them apart.
+    pub fn load_pending_steers(&self, voyage_id: Uuid) -> Result<Vec<PendingSteer>> {
+        let conn = self.open_voyage(voyage_id)?;
+
+        let mut stmt = conn.prepare(
+            "SELECT p.id, p.started_at, p.identity, p.action, p.summary, p.role, p.method,
+                    (SELECT COUNT(*) FROM pending_observations o WHERE o.pending_id = p.id),
+                    p.status, p.approval
+             FROM pending_steers p
+             ORDER BY p.id",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(PendingRow {
+                    id: row.get(0)?,
+                    started_at: row.get(1)?,
+                    provenance: Provenance {
+                        identity: row.get(2)?,
+                        role: row.get(5)?,
+                        method: row.get(6)?,
+                    },
+                    action_json: row.get(3)?,
+                    summary: row.get(4)?,
+                    observation_count: row.get(7)?,
+                    status: row.get(8)?,
+                    approval_json: row.get(9)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(PendingSteer {
+                    id: row.id,
+                    steer: serde_json::from_str(&row.action_json)?,
+                    summary: row.summary,
+                    provenance: row.provenance,
+                    started_at: row
+                        .started_at
+                        .parse()
+                        .map_err(|e: jiff::Error| StorageError::TimeParse(e.to_string()))?,
+                    observation_count: row.observation_count,
+                    status: if row.status == "proposed" {
+                        PendingStatus::Proposed
+                    } else {
+                        PendingStatus::Performing
+                    },
+                    approval: row
+                        .approval_json
+                        .map(|json| serde_json::from_str(&json))
+                        .transpose()?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// A `pending_steers` row as read, before decoding its JSON and timestamp columns.
+struct PendingRow {
+    id: i64,
+    started_at: String,
+    provenance: Provenance,
+    action_json: String,
+    summary: String,
+    observation_count: usize,
+    status: String,
+    approval_json: Option<String>,
+}
+
+/// Load the observations sealed into a pending steer, in slate order.
+fn load_pending_rows(
+    conn: &rusqlite::Connection,
+    pending_id: i64,
+) -> Result<Vec<super::logbook::SealedRow>> {
+    load_sealed_rows(
+        conn,
+        "SELECT target, artifact_hash, observed_at FROM pending_observations
+         WHERE pending_id = ?1
+         ORDER BY rowid",
+        rusqlite::params![pending_id],
+    )
+}
+
+/// Remove a pending steer and its sealed observations; fails if it is already gone.
+fn delete_pending(conn: &rusqlite::Connection, pending_id: i64) -> Result<()> {
+    conn.execute(
+        "DELETE FROM pending_observations WHERE pending_id = ?1",
+        rusqlite::params![pending_id],
+    )?;
+    let deleted = conn.execute(
+        "DELETE FROM pending_steers WHERE id = ?1",
+        rusqlite::params![pending_id],
+    )?;
+    if deleted == 0 {
+        return Err(StorageError::PendingSteerNotFound(pending_id));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::PathBuf;
+
+    use tempfile::TempDir;
+
+    use crate::model::{
+        CommentTarget, DirectoryEntry, DirectoryListing, Observation, Observe, Payload, Voyage,
+        VoyageStatus,
+    };
+
+    fn test_storage() -> (TempDir, Storage) {
+        let dir = TempDir::new().unwrap();
+        let storage = Storage::new(dir.path().join("voyages")).unwrap();
+        (dir, storage)
+    }
+
+    fn sample_voyage() -> Voyage {
+        Voyage {
+            id: Uuid::new_v4(),
+            intent: "Fix the widget".into(),
+            created_at: Timestamp::now(),
+            status: VoyageStatus::Active,
+        }
+    }
+
+    fn provenance() -> Provenance {
+        Provenance {
+            identity: "alice".into(),
+            role: "coder".into(),
+            method: "human".into(),
+        }
+    }
+
+    fn sample_steer() -> Steer {
+        Steer::Comment {
+            number: 42,
+            body: "Here's my plan.".into(),
+            target: CommentTarget::Issue,
+        }
+    }
+
+    fn sample_observation(root: &str) -> Observation {
+        Observation {
+            target: Observe::DirectoryTree {
+                root: PathBuf::from(root),
+                skip: vec![],
+                max_depth: None,
+            },
+            payload: Payload::DirectoryTree {
+                listings: vec![DirectoryListing {
+                    path: PathBuf::from(root),
+                    entries: vec![DirectoryEntry {
+                        name: "main.rs".into(),
+                        is_dir: false,
+                        size_bytes: Some(42),
+                    }],
+                }],
+            },
+            observed_at: Timestamp::now(),
+        }
+    }
+
+    #[test]
+    fn begin_steer_seals_slate_into_journal() {
+        let (_dir, storage) = test_storage();
+        let voyage = sample_voyage();
+        storage.create_voyage(&voyage).unwrap();
+        storage
+            .observe(voyage.id, &sample_observation("src/"))
+            .unwrap();
+
+        let id = storage
+            .begin_steer(voyage.id, &sample_steer(), "Ready to steer", &provenance())
+            .unwrap();
+
+        assert!(storage.load_slate(voyage.id).unwrap().is_empty());
+        let pending = storage.load_pending_steers(voyage.id).unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, id);
+        assert_eq!(pending[0].summary, "Ready to steer");
+        assert_eq!(pending[0].provenance.identity, "alice");
+        assert_eq!(pending[0].observation_count, 1);
+        assert!(storage.load_logbook(voyage.id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn pending_bearing_holds_sealed_observations() {
+        let (_dir, storage) = test_storage();
+        let voyage = sample_voyage();
+        storage.create_voyage(&voyage).unwrap();
+        storage
+            .observe(voyage.id, &sample_observation("src/"))
+            .unwrap();
+
+        let id = storage
+            .propose_steer(voyage.id, &sample_steer(), "Ready to steer", &provenance())
+            .unwrap();
+        storage
+            .observe(voyage.id, &sample_observation("docs/"))
+            .unwrap();
+
+        let bearing = storage.load_pending_bearing(voyage.id, id).unwrap();
+        assert_eq!(bearing.len(), 1);
+        assert!(matches!(
+            &bearing[0].target,
+            Observe::DirectoryTree { root, .. } if root == &PathBuf::from("src/")
+        ));
+    }
+
+    #[test]
+    fn complete_steer_records_sealed_bearing() {
+        let (_dir, storage) = test_storage();
+        let voyage = sample_voyage();
+        storage.create_voyage(&voyage).unwrap();
+        storage
+            .observe(voyage.id, &sample_observation("src/"))
+            .unwrap();
+
+        let id = storage
+            .begin_steer(voyage.id, &sample_steer(), "Ready to steer", &provenance())
+            .unwrap();
+        // Observed while the action runs — belongs to the next bearing.
+        storage
+            .observe(voyage.id, &sample_observation("docs/"))
+            .unwrap();
+        let outcome = SteerOutcome::CommentPosted {
+            id: 7,
+            url: "https://github.com/dyreby/helm/issues/42#issuecomment-7".into(),
+        };
+        storage
+            .complete_steer(voyage.id, id, Some(&outcome))
+            .unwrap();
+
+        assert!(storage.load_pending_steers(voyage.id).unwrap().is_empty());
+        assert_eq!(storage.load_slate(voyage.id).unwrap().len(), 1);
+
+        let entries = storage.load_logbook(voyage.id).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].bearing.summary, "Ready to steer");
+        assert_eq!(entries[0].bearing.observations.len(), 1);
+        assert!(matches!(
+            entries[0].kind,
+            EntryKind::Steer {
+                outcome: Some(SteerOutcome::CommentPosted { id: 7, .. }),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn complete_steer_on_empty_slate() {
+        let (_dir, storage) = test_storage();
+        let voyage = sample_voyage();
+        storage.create_voyage(&voyage).unwrap();
+
+        let id = storage
+            .begin_steer(voyage.id, &sample_steer(), "summary", &provenance())
+            .unwrap();
+        storage.complete_steer(voyage.id, id, None).unwrap();
+
+        let entries = storage.load_logbook(voyage.id).unwrap();
+        assert!(entries[0].bearing.observations.is_empty());
+    }
+
+    #[test]
+    fn abandon_steer_restores_slate() {
+        let (_dir, storage) = test_storage();
+        let voyage = sample_voyage();
+        storage.create_voyage(&voyage).unwrap();
+        storage
+            .observe(voyage.id, &sample_observation("src/"))
+            .unwrap();
+
+        let id = storage
+            .begin_steer(voyage.id, &sample_steer(), "summary", &provenance())
+            .unwrap();
+        storage.abandon_steer(voyage.id, id).unwrap();
+
+        assert!(storage.load_pending_steers(voyage.id).unwrap().is_empty());
+        assert!(storage.load_logbook(voyage.id).unwrap().is_empty());
+        let slate = storage.load_slate(voyage.id).unwrap();
+        assert_eq!(slate.len(), 1);
+    }
+
+    #[test]
+    fn abandon_steer_keeps_newer_observation() {
+        let (_dir, storage) = test_storage();
+        let voyage = sample_voyage();
+        storage.create_voyage(&voyage).unwrap();
+        let target = Observe::GitHubIssue { number: 1 };
+        let observation = |root: &str| Observation {
+            target: target.clone(),
+            ..sample_observation(root)
+        };
+        storage.observe(voyage.id, &observation("old/")).unwrap();
+
+        let id = storage
+            .begin_steer(voyage.id, &sample_steer(), "summary", &provenance())
+            .unwrap();
+        storage.observe(voyage.id, &observation("new/")).unwrap();
+        storage.abandon_steer(voyage.id, id).unwrap();
+
+        let slate = storage.load_slate(voyage.id).unwrap();
+        assert_eq!(slate.len(), 1);
+        let Payload::DirectoryTree { listings } = &slate[0].payload else {
+            panic!("expected DirectoryTree payload");
+        };
+        assert_eq!(listings[0].path, PathBuf::from("new/"));
+    }
+
+    #[test]
+    fn complete_unknown_pending_steer_fails() {
+        let (_dir, storage) = test_storage();
+        let voyage = sample_voyage();
+        storage.create_voyage(&voyage).unwrap();
+
+        let err = storage.complete_steer(voyage.id, 99, None).unwrap_err();
+        assert!(matches!(err, StorageError::PendingSteerNotFound(99)));
+        let err = storage.abandon_steer(voyage.id, 99).unwrap_err();
+        assert!(matches!(err, StorageError::PendingSteerNotFound(99)));
+    }
+
+    fn approval() -> Approval {
+        Approval {
+            provenance: Provenance {
+                identity: "bob".into(),
+                role: "reviewer".into(),
+                method: "human".into(),
+            },
+            summary: "Looks right".into(),
+            approved_at: Timestamp::now(),
+        }
+    }
+
+    #[test]
+    fn proposal_waits_for_approval() {
+        let (_dir, storage) = test_storage();
+        let voyage = sample_voyage();
+        storage.create_voyage(&voyage).unwrap();
+        storage
+            .observe(voyage.id, &sample_observation("src/"))
+            .unwrap();
+
+        let id = storage
+            .propose_steer(voyage.id, &sample_steer(), "Proposing", &provenance())
+            .unwrap();
+
+        assert!(storage.load_slate(voyage.id).unwrap().is_empty());
+        let pending = storage.load_pending_steers(voyage.id).unwrap();
+        assert_eq!(pending[0].id, id);
+        assert_eq!(pending[0].status, PendingStatus::Proposed);
+        assert_eq!(pending[0].observation_count, 1);
+        assert!(pending[0].approval.is_none());
+    }
+
+    #[test]
+    fn approved_proposal_records_both_identities() {
+        let (_dir, storage) = test_storage();
+        let voyage = sample_voyage();
+        storage.create_voyage(&voyage).unwrap();
+        storage
+            .observe(voyage.id, &sample_observation("src/"))
+            .unwrap();
+
+        let id = storage
+            .propose_steer(voyage.id, &sample_steer(), "Proposing", &provenance())
+            .unwrap();
+        storage.approve_steer(voyage.id, id, &approval()).unwrap();
+        assert_eq!(
+            storage.load_pending_steers(voyage.id).unwrap()[0].status,
+            PendingStatus::Performing
+        );
+        storage.complete_steer(voyage.id, id, None).unwrap();
+
+        let entries = storage.load_logbook(voyage.id).unwrap();
+        assert_eq!(entries[0].identity, "alice");
+        assert_eq!(entries[0].bearing.summary, "Proposing");
+        assert_eq!(entries[0].bearing.observations.len(), 1);
+        let approval = entries[0].approval.as_ref().unwrap();
+        assert_eq!(approval.provenance.identity, "bob");
+        assert_eq!(approval.summary, "Looks right");
+    }
+
+    #[test]
+    fn proposal_can_only_be_approved_once() {
+        let (_dir, storage) = test_storage();
+        let voyage = sample_voyage();
+        storage.create_voyage(&voyage).unwrap();
+
+        let id = storage
+            .propose_steer(voyage.id, &sample_steer(), "Proposing", &provenance())
+            .unwrap();
+        storage.approve_steer(voyage.id, id, &approval()).unwrap();
+
+        let err = storage
+            .approve_steer(voyage.id, id, &approval())
+            .unwrap_err();
+        assert!(matches!(err, StorageError::ProposalNotFound(_)));
+    }
+
+    #[test]
+    fn withdrawn_approval_returns_to_proposed() {
+        let (_dir, storage) = test_storage();
+        let voyage = sample_voyage();
+        storage.create_voyage(&voyage).unwrap();
+
+        let id = storage
+            .propose_steer(voyage.id, &sample_steer(), "Proposing", &provenance())
+            .unwrap();
+        storage.approve_steer(voyage.id, id, &approval()).unwrap();
+        storage.withdraw_approval(voyage.id, id).unwrap();
+
+        let pending = storage.load_pending_steers(voyage.id).unwrap();
+        assert_eq!(pending[0].status, PendingStatus::Proposed);
+        assert!(pending[0].approval.is_none());
+    }
+
+    #[test]
+    fn unapproved_steer_cannot_be_withdrawn() {
+        let (_dir, storage) = test_storage();
+        let voyage = sample_voyage();
+        storage.create_voyage(&voyage).unwrap();
+
+        let id = storage
+            .begin_steer(voyage.id, &sample_steer(), "summary", &provenance())
+            .unwrap();
+        assert!(storage.withdraw_approval(voyage.id, id).is_err());
+    }
+
+    #[test]
+    fn begin_steer_nonexistent_voyage_fails() {
+        let (_dir, storage) = test_storage();
+        let err = storage
+            .begin_steer(Uuid::new_v4(), &sample_steer(), "s", &provenance())
+            .unwrap_err();
+        assert!(matches!(err, StorageError::VoyageNotFound(_)));
+    }
+}
@@ -1,178 +1,341 @@
 //! Logbook storage: atomic seal-and-record operations.
 //!
-//! `record_steer` and `record_log` each perform a single transaction that:
+//! `record_log` performs a single transaction that:
 //! 1. Reads the current slate (`target`, `artifact_hash`, `observed_at` per row).
-//! 2. Inserts a logbook row.
+//! 2. Inserts a logbook row, with its signature if the acting identity has a key.
 //! 3. Copies slate rows into `bearing_observations`.
-//! 4. Clears the slate.
+//! 4. Links the row onto the logbook's hash chain (see `chain`).
+//! 5. Clears the slate.
+//!
+//! Signing shells out to `ssh-keygen`, so it happens before that transaction
+//! opens (see `signature`); the transaction checks nothing it covers has changed since.
+//!
+//! Steers are recorded through the journal instead (see `journal`), which
+//! seals the slate before the action runs and reuses `insert_entry` afterwards.
 //!
 //! The slate's artifacts are already stored by the time these methods run —
 //! `Storage::observe` handles that. The seal transaction only links existing
 //! artifacts to the new logbook entry.
 
 use jiff::Timestamp;
+use rusqlite::Connection;
 use uuid::Uuid;
 
-use crate::model::{Bearing, EntryKind, LogbookEntry, Observation, Observe, Steer};
+use crate::model::{Approval, Bearing, EntryKind, LogbookEntry, Observation, Observe, Provenance};
 
-use super::{Result, Storage, StorageError, load_artifact};
+use super::{
+    Result, Storage, StorageError,
+    chain::{self, EntryRow},
+    load_artifact,
+    search::{self, IndexedEntry},
+    signature::{self, Presigned},
+};
 
-impl Storage {
-    /// Seal the slate into a bearing, record a steer entry, and clear the slate.
-    ///
-    /// All four steps are one atomic transaction. If any step fails, the
-    /// logbook and slate are unchanged.
-    pub fn record_steer(
-        &self,
-        voyage_id: Uuid,
-        steer: &Steer,
-        summary: &str,
-        identity: &str,
-        role: &str,
-        method: &str,
-    ) -> Result<()> {
-        let action_json = serde_json::to_string(&EntryKind::Steer(steer.clone()))?;
-        self.record_entry(voyage_id, &action_json, summary, identity, role, method)
-    }
+/// A sealed observation as stored: target JSON, artifact hash, and observation time.
+pub(super) type SealedRow = (String, String, String);
 
+impl Storage {
     /// Seal the slate into a bearing, record a log entry, and clear the slate.
     ///
-    /// All four steps are one atomic transaction.
+    /// All five steps are one atomic transaction. If any step fails, the
+    /// logbook and slate are unchanged.
     pub fn record_log(
         &self,
         voyage_id: Uuid,
         status: &str,
         summary: &str,
-        identity: &str,
-        role: &str,
-        method: &str,
+        provenance: &Provenance,
     ) -> Result<()> {
         let action_json = serde_json::to_string(&EntryKind::Log(status.to_string()))?;
-        self.record_entry(voyage_id, &action_json, summary, identity, role, method)
+        let row = new_row(action_json, summary, provenance, None)?;
+
+        let mut conn = self.open_voyage(voyage_id)?;
+        let signed_rows = load_slate_rows(&conn)?;
+        let presigned = self.presign(&conn, &row, None, &signed_rows)?;
+
+        let tx = conn.transaction()?;
+        let slate_rows = load_slate_rows(&tx)?;
+        if presigned.is_some() && slate_rows != signed_rows {
+            return Err(StorageError::Signing(
+                "the slate changed while the entry was being signed; nothing was recorded".into(),
+            ));
+        }
+        insert_entry(&tx, &row, &slate_rows, presigned.as_ref())?;
+        tx.execute("DELETE FROM slate", [])?;
+        prune_artifacts(&tx)?;
+
+        tx.commit()?;
+        Ok(())
     }
 
     /// Load all logbook entries for a voyage.
     ///
     /// Each entry's bearing is reconstructed from `bearing_observations` joined with
     /// `artifacts`. Entries are returned in insertion order.
-    // TODO: remove once `helm log show` is built.
-    #[allow(dead_code)]
     pub fn load_logbook(&self, voyage_id: Uuid) -> Result<Vec<LogbookEntry>> {
         let conn = self.open_voyage(voyage_id)?;
 
-        let rows: Vec<(i64, String, String, String, String, String, String)> = {
+        let rows = {
             let mut stmt = conn.prepare(
-                "SELECT id, recorded_at, identity, action, summary, role, method
+                "SELECT id, recorded_at, identity, action, summary, role, method, approval
                  FROM logbook
                  ORDER BY id",
             )?;
             stmt.query_map([], |row| {
-                Ok((
-                    row.get::<_, i64>(0)?,
-                    row.get::<_, String>(1)?,
-                    row.get::<_, String>(2)?,
-                    row.get::<_, String>(3)?,
-                    row.get::<_, String>(4)?,
-                    row.get::<_, String>(5)?,
-                    row.get::<_, String>(6)?,
-                ))
+                Ok(LogbookRow {
+                    id: row.get(0)?,
+                    recorded_at: row.get(1)?,
+                    identity: row.get(2)?,
+                    action_json: row.get(3)?,
+                    summary: row.get(4)?,
+                    role: row.get(5)?,
+                    method: row.get(6)?,
+                    approval_json: row.get(7)?,
+                })
             })?
             .collect::<rusqlite::Result<Vec<_>>>()?
         };
 
         rows.into_iter()
-            .map(
-                |(id, recorded_at_str, identity, action_json, summary, role, method)| {
-                    let recorded_at = recorded_at_str
-                        .parse::<Timestamp>()
-                        .map_err(|e| StorageError::TimeParse(e.to_string()))?;
-
-                    let kind: EntryKind = serde_json::from_str(&action_json)?;
-
-                    let observations = load_bearing_observations(&conn, id)?;
-
-                    Ok(LogbookEntry {
-                        bearing: Bearing {
-                            observations,
-                            summary,
-                        },
-                        identity,
-                        role,
-                        method,
-                        recorded_at,
-                        kind,
+            .map(|row| decode_entry(&conn, row))
+            .collect()
+    }
+
+    /// Load the `number`th logbook entry of a voyage, counting from 1 in insertion order.
+    ///
+    /// Only that entry's bearing is loaded.
+    pub fn load_logbook_entry(&self, voyage_id: Uuid, number: usize) -> Result<LogbookEntry> {
+        let conn = self.open_voyage(voyage_id)?;
+        let offset = number
+            .checked_sub(1)
+            .and_then(|n| i64::try_from(n).ok())
+            .ok_or(StorageError::EntryNotFound(number))?;
+
+        let row = conn
+            .query_row(
+                "SELECT id, recorded_at, identity, action, summary, role, method, approval
+                 FROM logbook
+                 ORDER BY id
+                 LIMIT 1 OFFSET ?1",
+                rusqlite::params![offset],
+                |row| {
+                    Ok(LogbookRow {
+                        id: row.get(0)?,
+                        recorded_at: row.get(1)?,
+                        identity: row.get(2)?,
+                        action_json: row.get(3)?,
+                        summary: row.get(4)?,
+                        role: row.get(5)?,
+                        method: row.get(6)?,
+                        approval_json: row.get(7)?,
                     })
                 },
             )
-            .collect()
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => StorageError::EntryNotFound(number),
+                e => e.into(),
+            })?;
+        decode_entry(&conn, row)
     }
 }
 
+/// Decode a logbook row, rebuilding its bearing from `bearing_observations`.
+fn decode_entry(conn: &Connection, row: LogbookRow) -> Result<LogbookEntry> {
+    let recorded_at = row
+        .recorded_at
+        .parse::<Timestamp>()
+        .map_err(|e| StorageError::TimeParse(e.to_string()))?;
+
+    let kind: EntryKind = serde_json::from_str(&row.action_json)?;
+
+    let observations = load_bearing_observations(conn, row.id)?;
+    let approval = row
+        .approval_json
+        .map(|json| serde_json::from_str(&json))
+        .transpose()?;
+
+    Ok(LogbookEntry {
+        bearing: Bearing {
+            observations,
+            summary: row.summary,
+        },
+        identity: row.identity,
+        role: row.role,
+        method: row.method,
+        recorded_at,
+        kind,
+        approval,
+    })
+}
+
+/// A `logbook` row as read, before decoding its JSON and timestamp columns.
+struct LogbookRow {
+    id: i64,
+    recorded_at: String,
+    identity: String,
+    action_json: String,
+    summary: String,
+    role: String,
+    method: String,
+    approval_json: Option<String>,
+}
+
+/// Read `(target, artifact_hash, observed_at)` rows with the given query.
+///
+/// Collected eagerly — the prepared statement borrows the connection,
+/// and callers go on to write within the same transaction.
+pub(super) fn load_sealed_rows(
+    conn: &Connection,
+    sql: &str,
+    params: impl rusqlite::Params,
+) -> Result<Vec<SealedRow>> {
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt
+        .query_map(params, |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(rows)
+}
+
 impl Storage {
-    /// Inner implementation shared by `record_steer` and `record_log`.
-    fn record_entry(
+    /// Sign a new entry ahead of the transaction that records it,
+    /// when storage has keys and the signer has one.
+    pub(super) fn presign(
         &self,
-        voyage_id: Uuid,
-        action_json: &str,
-        summary: &str,
-        identity: &str,
-        role: &str,
-        method: &str,
-    ) -> Result<()> {
-        let mut conn = self.open_voyage(voyage_id)?;
-        let tx = conn.transaction()?;
-
-        // Collect slate rows before inserting — prepared statement borrows tx.
-        let slate_rows: Vec<(String, String, String)> = {
-            let mut stmt =
-                tx.prepare("SELECT target, artifact_hash, observed_at FROM slate ORDER BY rowid")?;
-            stmt.query_map([], |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    row.get::<_, String>(1)?,
-                    row.get::<_, String>(2)?,
-                ))
-            })?
-            .collect::<rusqlite::Result<Vec<_>>>()?
+        conn: &Connection,
+        row: &EntryRow,
+        approval: Option<&Approval>,
+        bearing: &[SealedRow],
+    ) -> Result<Option<Presigned>> {
+        let Some(keys) = &self.keys else {
+            return Ok(None);
         };
+        let signer = signature::signer(&row.identity, approval);
+        signature::presign(conn, keys, signer, row, bearing)
+    }
 
-        let now = Timestamp::now().to_string();
-        tx.execute(
-            "INSERT INTO logbook (recorded_at, identity, action, summary, role, method)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-            rusqlite::params![now, identity, action_json, summary, role, method],
-        )?;
-        let logbook_id = tx.last_insert_rowid();
-
-        for (target, artifact_hash, observed_at) in &slate_rows {
-            tx.execute(
-                "INSERT INTO bearing_observations
-                 (logbook_id, target, artifact_hash, observed_at)
-                 VALUES (?1, ?2, ?3, ?4)",
-                rusqlite::params![logbook_id, target, artifact_hash, observed_at],
-            )?;
+    /// Fail unless `signer` can sign: has no key, or one that signs.
+    ///
+    /// A steer's entry is signed only after its action has run, so this is
+    /// checked first — a key that can't sign would leave it unrecordable.
+    pub fn check_signer(&self, signer: &str) -> Result<()> {
+        if let Some(keys) = &self.keys {
+            keys.sign(signer, b"helm signing check")
+                .map_err(StorageError::Signing)?;
         }
+        Ok(())
+    }
+}
 
-        tx.execute("DELETE FROM slate", [])?;
-
-        // Prune artifacts no longer referenced by any slate row or sealed bearing.
-        // Orphans accumulate when the same target is re-observed (INSERT OR REPLACE
-        // on slate replaces the row but leaves the old artifact in place).
-        // Cleaning up here — inside the seal transaction — catches them at the
-        // natural boundary without adding cost to every observe.
-        tx.execute(
-            "DELETE FROM artifacts WHERE hash NOT IN (
-                 SELECT artifact_hash FROM slate
-                 UNION
-                 SELECT artifact_hash FROM bearing_observations
-             )",
-            [],
-        )?;
+/// The row for an entry recorded now.
+pub(super) fn new_row(
+    action_json: String,
+    summary: &str,
+    provenance: &Provenance,
+    approval: Option<&Approval>,
+) -> Result<EntryRow> {
+    Ok(EntryRow {
+        recorded_at: Timestamp::now().to_string(),
+        identity: provenance.identity.clone(),
+        role: provenance.role.clone(),
+        method: provenance.method.clone(),
+        action: action_json,
+        summary: summary.to_string(),
+        approval: approval.map(serde_json::to_string).transpose()?,
+    })
+}
 
-        tx.commit()?;
+fn load_slate_rows(conn: &Connection) -> Result<Vec<SealedRow>> {
+    load_sealed_rows(
+        conn,
+        "SELECT target, artifact_hash, observed_at FROM slate ORDER BY rowid",
+        rusqlite::params![],
+    )
+}
 
-        Ok(())
+/// Insert a logbook row with its bearing observations and any signature,
+/// chain it, and index it for search.
+///
+/// Runs inside the caller's transaction; clearing whatever the
+/// observations were sealed from is the caller's job. A signature made
+/// on top of a head that has since moved is refused.
+pub(super) fn insert_entry(
+    conn: &Connection,
+    row: &EntryRow,
+    observations: &[SealedRow],
+    presigned: Option<&Presigned>,
+) -> Result<()> {
+    if let Some(presigned) = presigned
+        && chain::head(conn)? != presigned.prev_hash
+    {
+        return Err(StorageError::Signing(
+            "the logbook moved on while the entry was being signed; nothing was recorded".into(),
+        ));
+    }
+    conn.execute(
+        "INSERT INTO logbook
+         (recorded_at, identity, action, summary, role, method, approval, signature)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        rusqlite::params![
+            row.recorded_at,
+            row.identity,
+            row.action,
+            row.summary,
+            row.role,
+            row.method,
+            row.approval,
+            presigned.map(|p| &p.signature),
+        ],
+    )?;
+    let logbook_id = conn.last_insert_rowid();
+
+    for (target, artifact_hash, observed_at) in observations {
+        conn.execute(
+            "INSERT INTO bearing_observations
+             (logbook_id, target, artifact_hash, observed_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![logbook_id, target, artifact_hash, observed_at],
+        )?;
     }
+    chain::link(conn, logbook_id)?;
+
+    search::index_entry(
+        conn,
+        &IndexedEntry {
+            logbook_id,
+            recorded_at: &row.recorded_at,
+            identity: &row.identity,
+            action_json: &row.action,
+            summary: &row.summary,
+            approval_json: row.approval.as_deref(),
+        },
+        observations,
+    )
+}
+
+/// Prune artifacts no longer referenced by the slate, a pending steer, or a sealed bearing.
+///
+/// Orphans accumulate when the same target is re-observed (INSERT OR REPLACE
+/// on slate replaces the row but leaves the old artifact in place).
+/// Cleaning up inside the seal transaction catches them at the
+/// natural boundary without adding cost to every observe.
+pub(super) fn prune_artifacts(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "DELETE FROM artifacts WHERE hash NOT IN (
+             SELECT artifact_hash FROM slate
+             UNION
+             SELECT artifact_hash FROM pending_observations
+             UNION
+             SELECT artifact_hash FROM bearing_observations
+         )",
+        [],
+    )?;
+    Ok(())
 }
 
 /// Load the observations stored in `bearing_observations` for a logbook entry.
@@ -180,25 +343,23 @@ fn load_bearing_observations(
     conn: &rusqlite::Connection,
     logbook_id: i64,
 ) -> Result<Vec<Observation>> {
-    let rows: Vec<(String, String, String)> = {
-        let mut stmt = conn.prepare(
-            "SELECT bo.target, bo.observed_at, bo.artifact_hash
-             FROM bearing_observations bo
-             WHERE bo.logbook_id = ?1
-             ORDER BY bo.rowid",
-        )?;
-        stmt.query_map(rusqlite::params![logbook_id], |row| {
-            Ok((
-                row.get::<_, String>(0)?,
-                row.get::<_, String>(1)?,
-                row.get::<_, String>(2)?,
-            ))
-        })?
-        .collect::<rusqlite::Result<Vec<_>>>()?
-    };
+    let rows = load_sealed_rows(
+        conn,
+        "SELECT target, artifact_hash, observed_at FROM bearing_observations
+         WHERE logbook_id = ?1
+         ORDER BY rowid",
+        rusqlite::params![logbook_id],
+    )?;
+    decode_sealed_rows(conn, rows)
+}
 
+/// Rebuild observations from sealed rows, loading each payload from its artifact.
+pub(super) fn decode_sealed_rows(
+    conn: &rusqlite::Connection,
+    rows: Vec<SealedRow>,
+) -> Result<Vec<Observation>> {
     rows.into_iter()
-        .map(|(target_json, observed_at_str, hash)| {
+        .map(|(target_json, hash, observed_at_str)| {
             let target: Observe = serde_json::from_str(&target_json)?;
             let observed_at = observed_at_str
                 .parse::<Timestamp>()
@@ -245,6 +406,14 @@ mod tests {
         }
     }
 
+    fn provenance(identity: &str, role: &str, method: &str) -> Provenance {
+        Provenance {
+            identity: identity.into(),
+            role: role.into(),
+            method: method.into(),
+        }
+    }
+
     fn sample_observation() -> crate::model::Observation {
         crate::model::Observation {
             target: Observe::DirectoryTree {
@@ -266,34 +435,6 @@ mod tests {
         }
     }
 
-    #[test]
-    fn record_steer_seals_and_clears_slate() {
-        let (_dir, storage) = test_storage();
-        let voyage = sample_voyage();
-        storage.create_voyage(&voyage).unwrap();
-        storage.observe(voyage.id, &sample_observation()).unwrap();
-
-        let steer = Steer::Comment {
-            number: 42,
-            body: "Here's my plan.".into(),
-            target: CommentTarget::Issue,
-        };
-        storage
-            .record_steer(
-                voyage.id,
-                &steer,
-                "Ready to steer",
-                "alice",
-                "coder",
-                "human",
-            )
-            .unwrap();
-
-        // Slate should be empty after sealing.
-        let slate = storage.load_slate(voyage.id).unwrap();
-        assert!(slate.is_empty());
-    }
-
     #[test]
     fn record_log_seals_and_clears_slate() {
         let (_dir, storage) = test_storage();
@@ -306,9 +447,7 @@ mod tests {
                 voyage.id,
                 "Waiting for review.",
                 "All looks good",
-                "alice",
-                "reviewer",
-                "human",
+                &provenance("alice", "reviewer", "human"),
             )
             .unwrap();
 
@@ -316,23 +455,6 @@ mod tests {
         assert!(slate.is_empty());
     }
 
-    #[test]
-    fn record_steer_on_empty_slate() {
-        let (_dir, storage) = test_storage();
-        let voyage = sample_voyage();
-        storage.create_voyage(&voyage).unwrap();
-
-        // Sealing an empty slate should succeed.
-        let steer = Steer::Comment {
-            number: 1,
-            body: "Empty slate steer.".into(),
-            target: CommentTarget::Issue,
-        };
-        storage
-            .record_steer(voyage.id, &steer, "summary", "alice", "coder", "human")
-            .unwrap();
-    }
-
     #[test]
     fn load_logbook_after_record() {
         let (_dir, storage) = test_storage();
@@ -345,32 +467,29 @@ mod tests {
             body: "Comment body.".into(),
             target: CommentTarget::Issue,
         };
-        storage
-            .record_steer(
+        let pending_id = storage
+            .begin_steer(
                 voyage.id,
                 &steer,
                 "Steering now",
-                "alice",
-                "coder",
-                "claude",
+                &provenance("alice", "coder", "claude"),
             )
             .unwrap();
+        storage.complete_steer(voyage.id, pending_id, None).unwrap();
 
         storage
             .record_log(
                 voyage.id,
                 "Waiting.",
                 "Logged state",
-                "bob",
-                "reviewer",
-                "human",
+                &provenance("bob", "reviewer", "human"),
             )
             .unwrap();
 
         let entries = storage.load_logbook(voyage.id).unwrap();
         assert_eq!(entries.len(), 2);
 
-        assert!(matches!(entries[0].kind, EntryKind::Steer(_)));
+        assert!(matches!(entries[0].kind, EntryKind::Steer { .. }));
         assert_eq!(entries[0].identity, "alice");
         assert_eq!(entries[0].role, "coder");
         assert_eq!(entries[0].method, "claude");
@@ -383,6 +502,56 @@ mod tests {
         assert_eq!(entries[1].bearing.observations.len(), 0);
     }
 
+    #[test]
+    fn load_logbook_entry_by_number() {
+        let (_dir, storage) = test_storage();
+        let voyage = sample_voyage();
+        storage.create_voyage(&voyage).unwrap();
+
+        let alice = provenance("alice", "coder", "claude");
+        storage
+            .record_log(voyage.id, "Oriented.", "First", &alice)
+            .unwrap();
+        storage.observe(voyage.id, &sample_observation()).unwrap();
+        storage
+            .record_log(voyage.id, "Waiting.", "Second", &alice)
+            .unwrap();
+
+        let entry = storage.load_logbook_entry(voyage.id, 2).unwrap();
+        assert_eq!(entry.bearing.summary, "Second");
+        assert_eq!(entry.bearing.observations.len(), 1);
+
+        for number in [0, 3] {
+            let err = storage.load_logbook_entry(voyage.id, number).unwrap_err();
+            assert!(matches!(err, StorageError::EntryNotFound(n) if n == number));
+        }
+    }
+
+    #[test]
+    fn load_logbook_reads_steers_recorded_before_outcomes() {
+        let (_dir, storage) = test_storage();
+        let voyage = sample_voyage();
+        storage.create_voyage(&voyage).unwrap();
+
+        // A steer entry as the first releases wrote it: the bare steer, no outcome.
+        let conn = storage.open_voyage(voyage.id).unwrap();
+        conn.execute(
+            "INSERT INTO logbook (recorded_at, identity, action, summary, role, method)
+             VALUES ('2026-01-05T12:00:00Z', 'alice', ?1, 'Replying', 'coder', 'human')",
+            [r#"{"kind":"steer","data":{"kind":"comment","number":42,"body":"On it.","target":{"kind":"issue"}}}"#],
+        )
+        .unwrap();
+
+        let entries = storage.load_logbook(voyage.id).unwrap();
+        assert!(matches!(
+            &entries[0].kind,
+            EntryKind::Steer {
+                steer: Steer::Comment { number: 42, body, .. },
+                outcome: None,
+            } if body == "On it."
+        ));
+    }
+
     #[test]
     fn seal_prunes_orphaned_artifacts() {
         let (_dir, storage) = test_storage();
@@ -426,7 +595,12 @@ mod tests {
 
         // Seal — the orphaned artifact should be pruned.
         storage
-            .record_log(voyage.id, "done", "summary", "alice", "coder", "human")
+            .record_log(
+                voyage.id,
+                "done",
+                "summary",
+                &provenance("alice", "coder", "human"),
+            )
             .unwrap();
 
         let conn = storage.open_voyage(voyage.id).unwrap();
@@ -438,16 +612,21 @@ mod tests {
     }
 
     #[test]
-    fn record_steer_nonexistent_voyage_fails() {
-        let (_dir, storage) = test_storage();
-        let steer = Steer::Comment {
-            number: 1,
-            body: "Body.".into(),
-            target: CommentTarget::Issue,
-        };
-        let err = storage
-            .record_steer(Uuid::new_v4(), &steer, "s", "i", "r", "m")
-            .unwrap_err();
-        assert!(matches!(err, StorageError::VoyageNotFound(_)));
+    fn signers_are_checked_before_acting() {
+        let (keys_dir, keys) = crate::signing::keys_for("alice");
+        let dir = TempDir::new().unwrap();
+        let storage = Storage::new(dir.path().join("voyages"))
+            .unwrap()
+            .with_keys(keys);
+
+        storage.check_signer("alice").unwrap();
+        storage.check_signer("bob").unwrap();
+
+        // A key that can't sign fails now, not after the steer has run.
+        std::fs::write(keys_dir.path().join("alice"), "not a key").unwrap();
+        assert!(matches!(
+            storage.check_signer("alice").unwrap_err(),
+            StorageError::Signing(_)
+        ));
     }
 }
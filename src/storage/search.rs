@@ -0,0 +1,505 @@
+//! Search storage: a full-text index over each voyage, queried across all of them.
+//!
+//! Every voyage database carries an FTS5 table, `search_index`, kept current
+//! on write: the intent when the voyage is created, its status when it ends,
+//! and each logbook entry as it is recorded — one row for the entry itself
+//! (summary, log status or steer text, approval) and one per sealed
+//! observation (the decompressed artifact's text: file contents, issue
+//! bodies, comments, diffs).
+//!
+//! Text is every string in the stored JSON but the `kind` tags, so new steer
+//! and payload fields become searchable without touching the index.
+//!
+//! `search` runs the query against each voyage and merges the hits by rank.
+//! BM25 scores come from separate indexes, so the merge is approximate.
+
+use jiff::Timestamp;
+use rusqlite::Connection;
+use serde_json::Value;
+
+use crate::model::{Voyage, VoyageStatus};
+
+use super::{Result, Storage, StorageError, load_artifact_json, logbook::SealedRow};
+
+/// Where a search hit was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HitSource {
+    /// The voyage's intent.
+    Intent,
+    /// The status the voyage ended with.
+    Ended,
+    /// A logbook entry, counting from 1.
+    Entry(usize),
+    /// An observation sealed into a logbook entry's bearing, both counting from 1.
+    Observation { entry: usize, observation: usize },
+}
+
+/// One match, with a snippet of the text around it.
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub voyage: Voyage,
+    pub source: HitSource,
+    /// Who recorded the entry; `None` for the intent and ended status.
+    pub identity: Option<String>,
+    pub at: Timestamp,
+    /// Matched terms are wrapped in `[` and `]`.
+    pub snippet: String,
+    /// BM25 rank: lower is better.
+    pub rank: f64,
+}
+
+/// Narrows a search. The default matches everything.
+#[derive(Debug, Clone, Default)]
+pub struct SearchFilter {
+    /// Only entries recorded by this identity (and their observations).
+    pub identity: Option<String>,
+    /// Only text recorded at or after this instant.
+    pub since: Option<Timestamp>,
+    /// Only text recorded before this instant.
+    pub until: Option<Timestamp>,
+    /// Only active (`false`) or ended (`true`) voyages.
+    pub ended: Option<bool>,
+}
+
+impl Storage {
+    /// Search every voyage for `query`, best matches first, at most `limit` hits.
+    ///
+    /// Each whitespace-separated word must appear; a trailing `*` matches a prefix.
+    pub fn search(
+        &self,
+        query: &str,
+        filter: &SearchFilter,
+        limit: usize,
+    ) -> Result<Vec<SearchHit>> {
+        let Some(fts_query) = fts_query(query) else {
+            return Ok(Vec::new());
+        };
+
+        let mut hits = Vec::new();
+        for voyage in self.list_voyages()? {
+            let ended = matches!(voyage.status, VoyageStatus::Ended { .. });
+            if filter.ended.is_some_and(|want| want != ended) {
+                continue;
+            }
+            let conn = self.open_voyage(voyage.id)?;
+            hits.extend(search_voyage(&conn, &voyage, &fts_query, filter, limit)?);
+        }
+
+        hits.sort_by(|a, b| a.rank.total_cmp(&b.rank));
+        hits.truncate(limit);
+        Ok(hits)
+    }
+}
+
+/// A row of `search_index` as read, before its entry number is resolved.
+struct IndexRow {
+    source: String,
+    entry_number: Option<usize>,
+    observation: Option<usize>,
+    identity: Option<String>,
+    at: String,
+    snippet: String,
+    rank: f64,
+}
+
+fn search_voyage(
+    conn: &Connection,
+    voyage: &Voyage,
+    fts_query: &str,
+    filter: &SearchFilter,
+    limit: usize,
+) -> Result<Vec<SearchHit>> {
+    let mut stmt = conn.prepare(
+        "SELECT source,
+                (SELECT COUNT(*) FROM logbook WHERE logbook.id <= search_index.logbook_id),
+                observation,
+                identity,
+                at,
+                snippet(search_index, 0, '[', ']', '…', 16),
+                rank
+         FROM search_index
+         WHERE search_index MATCH ?1
+           AND (?2 IS NULL OR identity = ?2)
+           AND (?3 IS NULL OR at >= ?3)
+           AND (?4 IS NULL OR at < ?4)
+         ORDER BY rank
+         LIMIT ?5",
+    )?;
+    let rows = stmt
+        .query_map(
+            rusqlite::params![
+                fts_query,
+                filter.identity,
+                filter.since.map(|t| t.to_string()),
+                filter.until.map(|t| t.to_string()),
+                i64::try_from(limit).unwrap_or(i64::MAX),
+            ],
+            |row| {
+                Ok(IndexRow {
+                    source: row.get(0)?,
+                    entry_number: row.get(1)?,
+                    observation: row.get(2)?,
+                    identity: row.get(3)?,
+                    at: row.get(4)?,
+                    snippet: row.get(5)?,
+                    rank: row.get(6)?,
+                })
+            },
+        )?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    rows.into_iter()
+        .map(|row| {
+            let source = match (row.source.as_str(), row.entry_number, row.observation) {
+                ("intent", ..) => HitSource::Intent,
+                ("ended", ..) => HitSource::Ended,
+                ("entry", Some(entry), _) => HitSource::Entry(entry),
+                ("observation", Some(entry), Some(observation)) => {
+                    HitSource::Observation { entry, observation }
+                }
+                (source, ..) => {
+                    return Err(StorageError::Db(rusqlite::Error::FromSqlConversionFailure(
+                        0,
+                        rusqlite::types::Type::Text,
+                        format!("malformed search index row: {source}").into(),
+                    )));
+                }
+            };
+            let at = row
+                .at
+                .parse::<Timestamp>()
+                .map_err(|e| StorageError::TimeParse(e.to_string()))?;
+            Ok(SearchHit {
+                voyage: voyage.clone(),
+                source,
+                identity: row.identity,
+                at,
+                snippet: row.snippet,
+                rank: row.rank,
+            })
+        })
+        .collect()
+}
+
+/// Turn free text into an FTS5 query: each word quoted, so punctuation
+/// such as `#42` or `foo-bar` is matched rather than parsed.
+fn fts_query(query: &str) -> Option<String> {
+    let terms: Vec<_> = query
+        .split_whitespace()
+        .filter_map(|word| {
+            let (word, prefix) = match word.strip_suffix('*') {
+                Some(stem) => (stem, "*"),
+                None => (word, ""),
+            };
+            (!word.is_empty()).then(|| format!("\"{}\"{prefix}", word.replace('"', "\"\"")))
+        })
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// Index the voyage's intent.
+pub(super) fn index_intent(conn: &Connection, intent: &str, created_at: &str) -> Result<()> {
+    insert(conn, intent, "intent", None, None, None, created_at)
+}
+
+/// Index the status a voyage ended with, replacing any indexed before.
+pub(super) fn index_ended(conn: &Connection, status: Option<&str>, ended_at: &str) -> Result<()> {
+    conn.execute("DELETE FROM search_index WHERE source = 'ended'", [])?;
+    match status {
+        Some(status) => insert(conn, status, "ended", None, None, None, ended_at),
+        None => Ok(()),
+    }
+}
+
+/// A logbook row's columns, as needed to index it.
+pub(super) struct IndexedEntry<'a> {
+    pub logbook_id: i64,
+    pub recorded_at: &'a str,
+    pub identity: &'a str,
+    pub action_json: &'a str,
+    pub summary: &'a str,
+    pub approval_json: Option<&'a str>,
+}
+
+/// Index a logbook entry and each observation sealed into its bearing.
+pub(super) fn index_entry(
+    conn: &Connection,
+    entry: &IndexedEntry<'_>,
+    observations: &[SealedRow],
+) -> Result<()> {
+    let mut text = entry.summary.to_string();
+    for json in std::iter::once(entry.action_json).chain(entry.approval_json) {
+        push_strings(&serde_json::from_str(json)?, &mut text);
+    }
+    insert(
+        conn,
+        &text,
+        "entry",
+        Some(entry.logbook_id),
+        None,
+        Some(entry.identity),
+        entry.recorded_at,
+    )?;
+
+    for (i, (_, artifact_hash, _)) in observations.iter().enumerate() {
+        let json = load_artifact_json(conn, artifact_hash)?;
+        let mut text = String::new();
+        push_strings(&serde_json::from_slice(&json)?, &mut text);
+        insert(
+            conn,
+            &text,
+            "observation",
+            Some(entry.logbook_id),
+            Some(i + 1),
+            Some(entry.identity),
+            entry.recorded_at,
+        )?;
+    }
+    Ok(())
+}
+
+/// Index everything already in a voyage — for databases that predate the index.
+pub(super) fn backfill(conn: &Connection) -> Result<()> {
+    let voyage: Option<(String, String, Option<String>, Option<String>)> = conn
+        .query_row(
+            "SELECT intent, created_at, ended_at, ended_status FROM voyage LIMIT 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .map(Some)
+        .or_else(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => Ok(None),
+            e => Err(e),
+        })?;
+    if let Some((intent, created_at, ended_at, ended_status)) = voyage {
+        index_intent(conn, &intent, &created_at)?;
+        if let Some(ended_at) = ended_at {
+            index_ended(conn, ended_status.as_deref(), &ended_at)?;
+        }
+    }
+
+    let entries = {
+        let mut stmt = conn.prepare(
+            "SELECT id, recorded_at, identity, action, summary, approval FROM logbook ORDER BY id",
+        )?;
+        stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, Option<String>>(5)?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?
+    };
+    for (logbook_id, recorded_at, identity, action_json, summary, approval_json) in entries {
+        let observations = super::logbook::load_sealed_rows(
+            conn,
+            "SELECT target, artifact_hash, observed_at FROM bearing_observations
+             WHERE logbook_id = ?1
+             ORDER BY rowid",
+            rusqlite::params![logbook_id],
+        )?;
+        index_entry(
+            conn,
+            &IndexedEntry {
+                logbook_id,
+                recorded_at: &recorded_at,
+                identity: &identity,
+                action_json: &action_json,
+                summary: &summary,
+                approval_json: approval_json.as_deref(),
+            },
+            &observations,
+        )?;
+    }
+    Ok(())
+}
+
+fn insert(
+    conn: &Connection,
+    text: &str,
+    source: &str,
+    logbook_id: Option<i64>,
+    observation: Option<usize>,
+    identity: Option<&str>,
+    at: &str,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO search_index (text, source, logbook_id, observation, identity, at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        rusqlite::params![text, source, logbook_id, observation, identity, at],
+    )?;
+    Ok(())
+}
+
+/// Append every string in `value` to `text`, one per line, skipping `kind` tags.
+fn push_strings(value: &Value, text: &mut String) {
+    match value {
+        Value::String(s) => {
+            if !text.is_empty() {
+                text.push('\n');
+            }
+            text.push_str(s);
+        }
+        Value::Array(items) => items.iter().for_each(|v| push_strings(v, text)),
+        Value::Object(fields) => fields
+            .iter()
+            .filter(|(key, _)| *key != "kind")
+            .for_each(|(_, v)| push_strings(v, text)),
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::PathBuf;
+
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    use crate::model::{
+        CommentTarget, FileContent, FileContents, Observation, Observe, Payload, Provenance, Steer,
+    };
+
+    fn test_storage() -> (TempDir, Storage) {
+        let dir = TempDir::new().unwrap();
+        let storage = Storage::new(dir.path().join("voyages")).unwrap();
+        (dir, storage)
+    }
+
+    fn create_voyage(storage: &Storage, intent: &str) -> Voyage {
+        let voyage = Voyage {
+            id: Uuid::new_v4(),
+            intent: intent.into(),
+            created_at: Timestamp::now(),
+            status: VoyageStatus::Active,
+        };
+        storage.create_voyage(&voyage).unwrap();
+        voyage
+    }
+
+    fn provenance(identity: &str) -> Provenance {
+        Provenance {
+            identity: identity.into(),
+            role: "coder".into(),
+            method: "conversation".into(),
+        }
+    }
+
+    fn file_observation(path: &str, content: &str) -> Observation {
+        Observation {
+            target: Observe::FileContents {
+                paths: vec![PathBuf::from(path)],
+            },
+            payload: Payload::FileContents {
+                contents: vec![FileContents {
+                    path: PathBuf::from(path),
+                    content: FileContent::Text {
+                        content: content.into(),
+                    },
+                }],
+            },
+            observed_at: Timestamp::now(),
+        }
+    }
+
+    #[test]
+    fn finds_entries_and_sealed_observations_across_voyages() {
+        let (_dir, storage) = test_storage();
+        let widget = create_voyage(&storage, "Fix the widget crash");
+        let docs = create_voyage(&storage, "Tidy the docs");
+
+        storage
+            .observe(
+                widget.id,
+                &file_observation("src/widget.rs", "fn spin() { panic!(\"gyroscope\") }"),
+            )
+            .unwrap();
+        let pending_id = storage
+            .begin_steer(
+                widget.id,
+                &Steer::Comment {
+                    number: 42,
+                    body: "The gyroscope overflows.".into(),
+                    target: CommentTarget::Issue,
+                },
+                "Found the cause",
+                &provenance("alice"),
+            )
+            .unwrap();
+        storage.complete_steer(widget.id, pending_id, None).unwrap();
+        storage
+            .record_log(docs.id, "waiting", "Nothing crashed", &provenance("bob"))
+            .unwrap();
+
+        let hits = storage
+            .search("gyroscope", &SearchFilter::default(), 10)
+            .unwrap();
+        let sources: Vec<_> = hits.iter().map(|h| h.source).collect();
+        assert_eq!(hits.len(), 2);
+        assert!(sources.contains(&HitSource::Entry(1)));
+        assert!(sources.contains(&HitSource::Observation {
+            entry: 1,
+            observation: 1
+        }));
+        assert!(hits.iter().all(|h| h.voyage.id == widget.id));
+
+        // Stemmed: "crashed" in one voyage, "crash" in the other's intent.
+        let hits = storage
+            .search("crash", &SearchFilter::default(), 10)
+            .unwrap();
+        assert_eq!(hits.len(), 2);
+
+        let by_bob = SearchFilter {
+            identity: Some("bob".into()),
+            ..SearchFilter::default()
+        };
+        let hits = storage.search("crash", &by_bob, 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].source, HitSource::Entry(1));
+        assert_eq!(hits[0].voyage.id, docs.id);
+    }
+
+    #[test]
+    fn filters_by_voyage_status_and_date() {
+        let (_dir, storage) = test_storage();
+        let mut voyage = create_voyage(&storage, "Ship the widget");
+        voyage.status = VoyageStatus::Ended {
+            ended_at: Timestamp::now(),
+            status: Some("Shipped the widget".into()),
+        };
+        storage.update_voyage(&voyage).unwrap();
+
+        let hits = storage
+            .search("widget", &SearchFilter::default(), 10)
+            .unwrap();
+        assert_eq!(hits.len(), 2);
+        assert!(hits.iter().any(|h| h.source == HitSource::Ended));
+
+        let active = SearchFilter {
+            ended: Some(false),
+            ..SearchFilter::default()
+        };
+        assert!(storage.search("widget", &active, 10).unwrap().is_empty());
+
+        let later = SearchFilter {
+            since: Some(Timestamp::now() + jiff::SignedDuration::from_hours(1)),
+            ..SearchFilter::default()
+        };
+        assert!(storage.search("widget", &later, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn punctuation_is_matched_not_parsed() {
+        assert_eq!(
+            fts_query("#42 foo-bar wid* \"x").unwrap(),
+            r##""#42" "foo-bar" "wid"* """x""##
+        );
+        assert!(fts_query("  ").is_none());
+    }
+}
@@ -0,0 +1,125 @@
+//! Entry signatures: each logbook row signed by whoever recorded it.
+//!
+//! The signer is the acting identity: the approver for an approved proposal,
+//! the entry's own identity otherwise. What is signed is the entry's
+//! canonical form (see `chain`), which covers the previous entry's hash, so
+//! a signature vouches for the entry and everything before it.
+//!
+//! Signatures live in `logbook.signature`, outside what the hash covers.
+//! That makes one easy to strip, so an unsigned entry from a signer who has
+//! a key is a flaw of its own — unless it was recorded before the voyage
+//! could sign (`voyage.signed_from`).
+
+use rusqlite::Connection;
+
+use crate::{
+    model::Approval,
+    signing::{Keys, Verdict},
+};
+
+use super::{
+    Result, StorageError,
+    chain::{self, EntryRow, canonical_entry},
+    logbook::SealedRow,
+};
+
+/// How an entry's signature checked out, when it doesn't simply verify.
+pub(super) enum Flaw {
+    /// No signature was recorded, and the signer has no key or the entry
+    /// predates signing.
+    Unsigned,
+    /// No signature was recorded, though the signer has a key: it was stripped.
+    Missing,
+    /// The signature doesn't match the signer's key or the entry.
+    Bad,
+    /// The signer has no public key to check against.
+    NoKey,
+}
+
+/// A signature made ahead of inserting its entry.
+pub(super) struct Presigned {
+    /// The head the entry was signed on top of.
+    pub prev_hash: Option<String>,
+    pub signature: String,
+}
+
+/// Sign an entry about to be recorded as `signer`, if `signer` has a key.
+///
+/// Runs before the transaction that inserts the entry, so `ssh-keygen` never
+/// runs while the voyage is locked for writing. The signature covers the
+/// entry on top of the current head; `insert_entry` refuses it if the head
+/// has moved on by the time the entry is inserted.
+pub(super) fn presign(
+    conn: &Connection,
+    keys: &Keys,
+    signer: &str,
+    row: &EntryRow,
+    bearing: &[SealedRow],
+) -> Result<Option<Presigned>> {
+    let prev_hash = chain::head(conn)?;
+    let data = chain::canonical(row, prev_hash.as_deref(), bearing)?;
+    let signature = keys.sign(signer, &data).map_err(StorageError::Signing)?;
+    Ok(signature.map(|signature| Presigned {
+        prev_hash,
+        signature,
+    }))
+}
+
+/// Check a logbook row's signature, returning who should have signed it and any flaw.
+pub(super) fn check(
+    conn: &Connection,
+    keys: &Keys,
+    logbook_id: i64,
+) -> Result<(String, Option<Flaw>)> {
+    let (identity, approval_json, signature): (String, Option<String>, Option<String>) = conn
+        .query_row(
+            "SELECT identity, approval, signature FROM logbook WHERE id = ?1",
+            rusqlite::params![logbook_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+    let approval: Option<Approval> = approval_json
+        .map(|json| serde_json::from_str(&json))
+        .transpose()?;
+    let signer = signer(&identity, approval.as_ref()).to_string();
+
+    let Some(signature) = signature else {
+        let signed_from: i64 =
+            conn.query_row("SELECT signed_from FROM voyage LIMIT 1", [], |row| {
+                row.get(0)
+            })?;
+        let keyed = keys
+            .has_public_key(&signer)
+            .map_err(StorageError::Signing)?;
+        let flaw = if keyed && logbook_id >= signed_from {
+            Flaw::Missing
+        } else {
+            Flaw::Unsigned
+        };
+        return Ok((signer, Some(flaw)));
+    };
+    let data = canonical(conn, logbook_id)?;
+    let flaw = match keys
+        .verify(&signer, &data, &signature)
+        .map_err(StorageError::Signing)?
+    {
+        Verdict::Good => None,
+        Verdict::Bad => Some(Flaw::Bad),
+        Verdict::NoKey => Some(Flaw::NoKey),
+    };
+    Ok((signer, flaw))
+}
+
+/// Who signs an entry: the approver if there is one, else whoever recorded it.
+pub(super) fn signer<'a>(identity: &'a str, approval: Option<&'a Approval>) -> &'a str {
+    approval.map_or(identity, |a| &a.provenance.identity)
+}
+
+/// The bytes a row's signature covers: its canonical form, with its stored `prev_hash`.
+fn canonical(conn: &Connection, logbook_id: i64) -> Result<Vec<u8>> {
+    let prev_hash: Option<String> = conn.query_row(
+        "SELECT prev_hash FROM logbook WHERE id = ?1",
+        rusqlite::params![logbook_id],
+        |row| row.get(0),
+    )?;
+    canonical_entry(conn, logbook_id, prev_hash.as_deref())
+}
@@ -7,7 +7,7 @@ use uuid::Uuid;
 
 use crate::model::{Voyage, VoyageStatus};
 
-use super::{Result, SCHEMA_DDL, Storage, StorageError};
+use super::{Result, SCHEMA_DDL, Storage, StorageError, migrate, search};
 
 impl Storage {
     /// Creates a new voyage, initialising a fresh `SQLite` database for it.
@@ -18,6 +18,7 @@ impl Storage {
         }
         let conn = rusqlite::Connection::open(&path)?;
         conn.execute_batch(SCHEMA_DDL)?;
+        migrate(&conn)?;
 
         let (status, ended_at, ended_status) = encode_status(&voyage.status);
         conn.execute(
@@ -32,15 +33,17 @@ impl Storage {
                 ended_status,
             ],
         )?;
+        search::index_intent(&conn, &voyage.intent, &voyage.created_at.to_string())?;
 
         Ok(())
     }
 
     /// Updates a voyage's metadata (used to transition status to ended).
     pub fn update_voyage(&self, voyage: &Voyage) -> Result<()> {
-        let conn = self.open_voyage(voyage.id)?;
+        let mut conn = self.open_voyage(voyage.id)?;
+        let tx = conn.transaction()?;
         let (status, ended_at, ended_status) = encode_status(&voyage.status);
-        let affected = conn.execute(
+        let affected = tx.execute(
             "UPDATE voyage SET status = ?1, ended_at = ?2, ended_status = ?3 WHERE id = ?4",
             rusqlite::params![status, ended_at, ended_status, voyage.id.to_string()],
         )?;
@@ -48,7 +51,11 @@ impl Storage {
         if affected == 0 {
             return Err(StorageError::VoyageNotFound(voyage.id));
         }
+        if let Some(ended_at) = &ended_at {
+            search::index_ended(&tx, ended_status.as_deref(), ended_at)?;
+        }
 
+        tx.commit()?;
         Ok(())
     }
 
@@ -99,7 +106,7 @@ impl Storage {
             }
         }
 
-        voyages.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        voyages.sort_by_key(|v| v.created_at);
         Ok(voyages)
     }
 }
@@ -174,6 +181,8 @@ mod tests {
     use jiff::Timestamp;
     use tempfile::TempDir;
 
+    use crate::storage::{MIGRATIONS, SearchFilter};
+
     fn test_storage() -> (TempDir, Storage) {
         let dir = TempDir::new().unwrap();
         let storage = Storage::new(dir.path().join("voyages")).unwrap();
@@ -274,4 +283,87 @@ mod tests {
         assert_eq!(voyages[0].intent, "First");
         assert_eq!(voyages[1].intent, "Second");
     }
+
+    #[test]
+    fn read_only_storage_leaves_the_schema_and_data_alone() {
+        let (_dir, storage) = test_storage();
+        let voyage = sample_voyage();
+
+        // A database as `create_voyage` made it before any migrations existed.
+        let conn = rusqlite::Connection::open(storage.voyage_path(voyage.id)).unwrap();
+        conn.execute_batch(SCHEMA_DDL).unwrap();
+        conn.execute(
+            "INSERT INTO voyage (id, intent, created_at, status) VALUES (?1, ?2, ?3, 'active')",
+            rusqlite::params![
+                voyage.id.to_string(),
+                voyage.intent,
+                voyage.created_at.to_string()
+            ],
+        )
+        .unwrap();
+        drop(conn);
+
+        // It reads through an upgraded copy, leaving the file at version 1.
+        let read_only = storage.read_only();
+        assert!(read_only.load_slate(voyage.id).unwrap().is_empty());
+        assert_eq!(
+            read_only.load_voyage(voyage.id).unwrap().intent,
+            voyage.intent
+        );
+        let conn = rusqlite::Connection::open(storage.voyage_path(voyage.id)).unwrap();
+        let version: usize = conn
+            .query_row("PRAGMA user_version", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(version, 1);
+        drop(conn);
+
+        // Once upgraded, it reads — and refuses to write.
+        storage.load_slate(voyage.id).unwrap();
+        assert!(read_only.load_slate(voyage.id).unwrap().is_empty());
+        assert!(matches!(
+            read_only.update_voyage(&voyage).unwrap_err(),
+            StorageError::Db(_)
+        ));
+    }
+
+    #[test]
+    fn open_migrates_older_schema() {
+        let (_dir, storage) = test_storage();
+        let voyage = sample_voyage();
+
+        // A database as `create_voyage` made it before any migrations existed.
+        let conn = rusqlite::Connection::open(storage.voyage_path(voyage.id)).unwrap();
+        conn.execute_batch(SCHEMA_DDL).unwrap();
+        conn.execute(
+            "INSERT INTO voyage (id, intent, created_at, status) VALUES (?1, ?2, ?3, 'active')",
+            rusqlite::params![
+                voyage.id.to_string(),
+                voyage.intent,
+                voyage.created_at.to_string()
+            ],
+        )
+        .unwrap();
+        drop(conn);
+
+        let conn = storage.open_voyage(voyage.id).unwrap();
+        let version: usize = conn
+            .query_row("PRAGMA user_version", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(version, MIGRATIONS.len() + 1);
+        assert!(storage.load_pending_steers(voyage.id).unwrap().is_empty());
+        assert!(storage.load_logbook(voyage.id).unwrap().is_empty());
+
+        // The intent was indexed on the way up.
+        let hits = storage
+            .search("widget", &SearchFilter::default(), 10)
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert!(
+            storage
+                .verify_logbook(voyage.id)
+                .unwrap()
+                .problems
+                .is_empty()
+        );
+    }
 }